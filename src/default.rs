@@ -1,7 +1,9 @@
 mod logger;
+mod logger_build_error;
 mod logger_builder;
 mod logger_service;
 
 pub use logger::{DefaultLogger, LocalConsoleLogger, LoggerServiceDispatcher};
+pub use logger_build_error::LoggerBuildError;
 pub use logger_builder::DefaultLoggerBuilder;
 pub use logger_service::{DefaultLoggerService, LoggerServiceEvent, ServiceLoggerImpl};
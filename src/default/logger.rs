@@ -14,17 +14,82 @@ mod airbrake;
 #[cfg(any(feature = "airbrake"))]
 pub use airbrake::AirbrakeLogger;
 
+#[cfg(any(feature = "loki"))]
+mod loki;
+#[cfg(any(feature = "loki"))]
+pub use loki::LokiLogger;
+#[cfg(any(feature = "loki"))]
+pub(crate) use loki::LOKI_PUSH_PATH;
+
+#[cfg(any(feature = "elasticsearch"))]
+mod elasticsearch;
+#[cfg(any(feature = "elasticsearch"))]
+pub use elasticsearch::ElasticsearchLogger;
+#[cfg(any(feature = "elasticsearch"))]
+pub(crate) use elasticsearch::ELASTICSEARCH_BULK_PATH;
+
+#[cfg(any(feature = "splunk"))]
+mod splunk;
+#[cfg(any(feature = "splunk"))]
+pub use splunk::SplunkLogger;
+#[cfg(any(feature = "splunk"))]
+pub(crate) use splunk::SPLUNK_EVENT_PATH;
+
+#[cfg(any(feature = "sentry"))]
+mod sentry;
+#[cfg(any(feature = "sentry"))]
+pub use sentry::SentryLogger;
+
+#[cfg(any(feature = "otlp"))]
+mod otlp;
+#[cfg(any(feature = "otlp"))]
+pub use otlp::OtlpLogger;
+#[cfg(any(feature = "otlp"))]
+pub(crate) use otlp::OTLP_LOGS_PATH;
+
+#[cfg(any(feature = "syslog"))]
+mod syslog;
+#[cfg(any(feature = "syslog"))]
+pub use syslog::SyslogLogger;
+#[cfg(all(target_os = "linux", feature = "journald"))]
+mod journald;
+#[cfg(all(target_os = "linux", feature = "journald"))]
+pub use journald::JournaldLogger;
+#[cfg(any(feature = "gelf"))]
+mod gelf;
+#[cfg(any(feature = "gelf"))]
+pub use gelf::GelfLogger;
+#[cfg(any(feature = "fluentd"))]
+mod fluentd;
+#[cfg(any(feature = "fluentd"))]
+pub use fluentd::FluentdLogger;
+
 mod service_dispatcher;
 pub use service_dispatcher::LoggerServiceDispatcher;
 
 use crate::{Event, Logger, Metadata};
 
+// built once and shared behind an `Arc`, boxing the sync logger would not save anything.
+#[allow(clippy::large_enum_variant)]
 pub enum DefaultLogger {
     Sync(SyncLoggerImpl),
     SyncMulti(Vec<SyncLoggerImpl>),
     Async(LoggerServiceDispatcher),
 }
 
+impl DefaultLogger {
+    /// Number of logs the tcp logger could neither send nor keep in its backlog, see
+    /// [`TcpLogger::dropped_events`].
+    #[cfg(any(feature = "tcp"))]
+    pub fn tcp_dropped_events(&self) -> u64 {
+        match self {
+            DefaultLogger::Async(inner) => inner.tcp_dropped_events(),
+            // the tcp logger is only supported in async mode.
+            DefaultLogger::Sync(_) | DefaultLogger::SyncMulti(_) => 0,
+        }
+    }
+}
+
 impl Logger for DefaultLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
         match &self {
@@ -46,7 +111,7 @@ impl Logger for DefaultLogger {
             DefaultLogger::Sync(logger) => Logger::record(logger, event),
             DefaultLogger::SyncMulti(loggers) => {
                 for logger in loggers {
-                    if logger.enabled(&event.metadata()) {
+                    if logger.enabled(event.metadata()) {
                         Logger::record(logger, event);
                     }
                 }
@@ -68,6 +133,7 @@ impl Logger for DefaultLogger {
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 pub enum SyncLoggerImpl {
     LocalConsole(LocalConsoleLogger),
     LocalFile(LocalFileLogger),
@@ -75,6 +141,24 @@ pub enum SyncLoggerImpl {
     Tcp(TcpLogger),
     #[cfg(any(feature = "airbrake"))]
     Airbrake(AirbrakeLogger),
+    #[cfg(any(feature = "loki"))]
+    Loki(LokiLogger),
+    #[cfg(any(feature = "elasticsearch"))]
+    Elasticsearch(ElasticsearchLogger),
+    #[cfg(any(feature = "splunk"))]
+    Splunk(SplunkLogger),
+    #[cfg(any(feature = "sentry"))]
+    Sentry(SentryLogger),
+    #[cfg(any(feature = "otlp"))]
+    Otlp(OtlpLogger),
+    #[cfg(any(feature = "syslog"))]
+    Syslog(SyslogLogger),
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    Journald(JournaldLogger),
+    #[cfg(any(feature = "gelf"))]
+    Gelf(GelfLogger),
+    #[cfg(any(feature = "fluentd"))]
+    Fluentd(FluentdLogger),
 }
 
 impl Logger for SyncLoggerImpl {
@@ -86,6 +170,24 @@ impl Logger for SyncLoggerImpl {
             SyncLoggerImpl::Tcp(inner) => inner.enabled(metadata),
             #[cfg(any(feature = "airbrake"))]
             SyncLoggerImpl::Airbrake(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "loki"))]
+            SyncLoggerImpl::Loki(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "elasticsearch"))]
+            SyncLoggerImpl::Elasticsearch(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "splunk"))]
+            SyncLoggerImpl::Splunk(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "sentry"))]
+            SyncLoggerImpl::Sentry(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "otlp"))]
+            SyncLoggerImpl::Otlp(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "syslog"))]
+            SyncLoggerImpl::Syslog(inner) => inner.enabled(metadata),
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            SyncLoggerImpl::Journald(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "gelf"))]
+            SyncLoggerImpl::Gelf(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "fluentd"))]
+            SyncLoggerImpl::Fluentd(inner) => inner.enabled(metadata),
         }
     }
 
@@ -97,6 +199,24 @@ impl Logger for SyncLoggerImpl {
             SyncLoggerImpl::Tcp(inner) => Logger::record(inner, event),
             #[cfg(any(feature = "airbrake"))]
             SyncLoggerImpl::Airbrake(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "loki"))]
+            SyncLoggerImpl::Loki(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "elasticsearch"))]
+            SyncLoggerImpl::Elasticsearch(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "splunk"))]
+            SyncLoggerImpl::Splunk(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "sentry"))]
+            SyncLoggerImpl::Sentry(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "otlp"))]
+            SyncLoggerImpl::Otlp(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "syslog"))]
+            SyncLoggerImpl::Syslog(inner) => Logger::record(inner, event),
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            SyncLoggerImpl::Journald(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "gelf"))]
+            SyncLoggerImpl::Gelf(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "fluentd"))]
+            SyncLoggerImpl::Fluentd(inner) => Logger::record(inner, event),
         }
     }
 
@@ -108,6 +228,24 @@ impl Logger for SyncLoggerImpl {
             SyncLoggerImpl::Tcp(inner) => inner.flush(),
             #[cfg(any(feature = "airbrake"))]
             SyncLoggerImpl::Airbrake(inner) => inner.flush(),
+            #[cfg(any(feature = "loki"))]
+            SyncLoggerImpl::Loki(inner) => inner.flush(),
+            #[cfg(any(feature = "elasticsearch"))]
+            SyncLoggerImpl::Elasticsearch(inner) => inner.flush(),
+            #[cfg(any(feature = "splunk"))]
+            SyncLoggerImpl::Splunk(inner) => inner.flush(),
+            #[cfg(any(feature = "sentry"))]
+            SyncLoggerImpl::Sentry(inner) => inner.flush(),
+            #[cfg(any(feature = "otlp"))]
+            SyncLoggerImpl::Otlp(inner) => inner.flush(),
+            #[cfg(any(feature = "syslog"))]
+            SyncLoggerImpl::Syslog(inner) => inner.flush(),
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            SyncLoggerImpl::Journald(inner) => inner.flush(),
+            #[cfg(any(feature = "gelf"))]
+            SyncLoggerImpl::Gelf(inner) => inner.flush(),
+            #[cfg(any(feature = "fluentd"))]
+            SyncLoggerImpl::Fluentd(inner) => inner.flush(),
         }
     }
 }
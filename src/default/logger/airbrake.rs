@@ -5,6 +5,7 @@ use crate::{
     AsyncEvent, AsyncLogger, Event, Logger, Metadata, StandardFilter, StandardFilterBuilder,
     Writer,
 };
+use http::uri::InvalidUri;
 
 pub struct AirbrakeLogger {
     printer: HttpWriter,
@@ -14,16 +15,20 @@ pub struct AirbrakeLogger {
 
 impl AirbrakeLogger {
     pub fn new(endpoint: String, environment: Option<String>) -> Self {
-        let printer = HttpWriter::new(endpoint);
+        Self::try_new(endpoint, environment).expect("http endpoint must be valid...")
+    }
+
+    pub fn try_new(endpoint: String, environment: Option<String>) -> Result<Self, InvalidUri> {
+        let printer = HttpWriter::try_new(endpoint)?;
         let filter = StandardFilterBuilder::new()
             .filter_level(LevelFilter::Error)
             .build();
         let formatter = AirbrakeFormatter::new(environment);
-        Self {
+        Ok(Self {
             printer,
             filter,
             formatter,
-        }
+        })
     }
 }
 
@@ -37,7 +42,9 @@ impl Logger for AirbrakeLogger {
         self.printer.write(s);
     }
 
-    fn flush(&self) {}
+    fn flush(&self) {
+        self.printer.flush()
+    }
 }
 
 impl AsyncLogger for AirbrakeLogger {
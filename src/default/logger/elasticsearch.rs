@@ -0,0 +1,37 @@
+use crate::{
+    filter::Filter,
+    formatter::{AsyncFormatter, ElasticsearchFormatter, Formatter},
+    writer::HttpWriter,
+    AsyncEvent, AsyncLogger, Event, Logger, Metadata, StandardFilter, Writer,
+};
+
+/// Path of the bulk api below the Elasticsearch or OpenSearch base url.
+pub(crate) const ELASTICSEARCH_BULK_PATH: &str = "/_bulk";
+
+pub struct ElasticsearchLogger {
+    pub(crate) printer: HttpWriter,
+    pub(crate) filter: StandardFilter,
+    pub(crate) formatter: ElasticsearchFormatter,
+}
+
+impl Logger for ElasticsearchLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+
+    fn record(&self, event: &Event) {
+        let s = Formatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+
+    fn flush(&self) {
+        self.printer.flush()
+    }
+}
+
+impl AsyncLogger for ElasticsearchLogger {
+    fn record(&self, event: &AsyncEvent) {
+        let s = AsyncFormatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{
+    filter::Filter,
+    formatter::{AsyncFormatter, FluentdFormatter, Formatter},
+    writer::FluentdWriter,
+    AsyncEvent, AsyncLogger, Event, Logger, Metadata, StandardFilter, Writer,
+};
+
+pub struct FluentdLogger {
+    pub(crate) printer: FluentdWriter,
+    pub(crate) filter: StandardFilter,
+    pub(crate) formatter: FluentdFormatter,
+}
+
+impl Logger for FluentdLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+
+    fn record(&self, event: &Event) {
+        let s = Formatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+
+    fn flush(&self) {
+        self.printer.flush()
+    }
+}
+
+impl AsyncLogger for FluentdLogger {
+    fn record(&self, event: &AsyncEvent) {
+        let s = AsyncFormatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{
+    filter::Filter,
+    formatter::{AsyncFormatter, Formatter, GelfFormatter},
+    writer::GelfWriter,
+    AsyncEvent, AsyncLogger, Event, Logger, Metadata, StandardFilter, Writer,
+};
+
+pub struct GelfLogger {
+    pub(crate) printer: GelfWriter,
+    pub(crate) filter: StandardFilter,
+    pub(crate) formatter: GelfFormatter,
+}
+
+impl Logger for GelfLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+
+    fn record(&self, event: &Event) {
+        let s = Formatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+
+    fn flush(&self) {
+        self.printer.flush()
+    }
+}
+
+impl AsyncLogger for GelfLogger {
+    fn record(&self, event: &AsyncEvent) {
+        let s = AsyncFormatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+}
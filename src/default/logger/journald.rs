@@ -0,0 +1,32 @@
+use crate::{
+    filter::Filter,
+    formatter::{AsyncFormatter, Formatter, JournaldFormatter},
+    writer::JournaldWriter,
+    AsyncEvent, AsyncLogger, Event, Logger, Metadata, StandardFilter, Writer,
+};
+
+pub struct JournaldLogger {
+    pub(crate) printer: JournaldWriter,
+    pub(crate) filter: StandardFilter,
+    pub(crate) formatter: JournaldFormatter,
+}
+
+impl Logger for JournaldLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+
+    fn record(&self, event: &Event) {
+        let s = Formatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+
+    fn flush(&self) {}
+}
+
+impl AsyncLogger for JournaldLogger {
+    fn record(&self, event: &AsyncEvent) {
+        let s = AsyncFormatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+}
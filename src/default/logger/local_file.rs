@@ -21,7 +21,9 @@ impl Logger for LocalFileLogger {
         self.printer.write(s);
     }
 
-    fn flush(&self) {}
+    fn flush(&self) {
+        self.printer.flush()
+    }
 }
 
 impl AsyncLogger for LocalFileLogger {
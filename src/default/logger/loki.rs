@@ -0,0 +1,37 @@
+use crate::{
+    filter::Filter,
+    formatter::{AsyncFormatter, Formatter, LokiFormatter},
+    writer::HttpWriter,
+    AsyncEvent, AsyncLogger, Event, Logger, Metadata, StandardFilter, Writer,
+};
+
+/// Path of the push api below the Loki base url.
+pub(crate) const LOKI_PUSH_PATH: &str = "/loki/api/v1/push";
+
+pub struct LokiLogger {
+    pub(crate) printer: HttpWriter,
+    pub(crate) filter: StandardFilter,
+    pub(crate) formatter: LokiFormatter,
+}
+
+impl Logger for LokiLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+
+    fn record(&self, event: &Event) {
+        let s = Formatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+
+    fn flush(&self) {
+        self.printer.flush()
+    }
+}
+
+impl AsyncLogger for LokiLogger {
+    fn record(&self, event: &AsyncEvent) {
+        let s = AsyncFormatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+}
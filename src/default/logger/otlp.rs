@@ -0,0 +1,37 @@
+use crate::{
+    filter::Filter,
+    formatter::{AsyncFormatter, Formatter, OtlpFormatter},
+    writer::HttpWriter,
+    AsyncEvent, AsyncLogger, Event, Logger, Metadata, StandardFilter, Writer,
+};
+
+/// Path of the logs endpoint below the OTLP/HTTP collector base url.
+pub(crate) const OTLP_LOGS_PATH: &str = "/v1/logs";
+
+pub struct OtlpLogger {
+    pub(crate) printer: HttpWriter,
+    pub(crate) filter: StandardFilter,
+    pub(crate) formatter: OtlpFormatter,
+}
+
+impl Logger for OtlpLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+
+    fn record(&self, event: &Event) {
+        let s = Formatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+
+    fn flush(&self) {
+        self.printer.flush()
+    }
+}
+
+impl AsyncLogger for OtlpLogger {
+    fn record(&self, event: &AsyncEvent) {
+        let s = AsyncFormatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+}
@@ -1,13 +1,28 @@
 use crate::default::logger_service::LoggerServiceEvent;
 use crate::{AsyncEvent, Event, Filter, Metadata, StandardFilter};
 use std::sync::mpsc::{self, SyncSender};
+#[cfg(any(feature = "tcp"))]
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 
 pub struct LoggerServiceDispatcher {
     pub(crate) filters: Vec<StandardFilter>,
     pub(crate) sender: SyncSender<LoggerServiceEvent>,
+    #[cfg(any(feature = "tcp"))]
+    pub(crate) tcp_dropped_events: Option<Arc<AtomicU64>>,
 }
 
 impl LoggerServiceDispatcher {
+    #[cfg(any(feature = "tcp"))]
+    pub(crate) fn tcp_dropped_events(&self) -> u64 {
+        self.tcp_dropped_events
+            .as_ref()
+            .map(|dropped_events| dropped_events.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
     pub(crate) fn enabled(&self, metadata: &Metadata) -> bool {
         for filter in &self.filters {
             if filter.enabled(metadata) {
@@ -18,7 +33,7 @@ impl LoggerServiceDispatcher {
     }
 
     pub(crate) fn record(&self, event: &Event) {
-        let timestamp = event.timestamp().clone();
+        let timestamp = *event.timestamp();
         let metadata = event.metadata().clone();
         let message = event.message().map(std::fmt::format);
         let keys_and_values = event.get_json_keys_and_values();
@@ -0,0 +1,37 @@
+use crate::{
+    filter::Filter,
+    formatter::{AsyncFormatter, Formatter, SplunkFormatter},
+    writer::HttpWriter,
+    AsyncEvent, AsyncLogger, Event, Logger, Metadata, StandardFilter, Writer,
+};
+
+/// Path of the event endpoint below the Splunk HTTP Event Collector base url.
+pub(crate) const SPLUNK_EVENT_PATH: &str = "/services/collector/event";
+
+pub struct SplunkLogger {
+    pub(crate) printer: HttpWriter,
+    pub(crate) filter: StandardFilter,
+    pub(crate) formatter: SplunkFormatter,
+}
+
+impl Logger for SplunkLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+
+    fn record(&self, event: &Event) {
+        let s = Formatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+
+    fn flush(&self) {
+        self.printer.flush()
+    }
+}
+
+impl AsyncLogger for SplunkLogger {
+    fn record(&self, event: &AsyncEvent) {
+        let s = AsyncFormatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+}
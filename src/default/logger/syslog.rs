@@ -0,0 +1,34 @@
+use crate::{
+    filter::Filter,
+    formatter::{AsyncFormatter, Formatter, SyslogFormatter},
+    writer::SyslogWriter,
+    AsyncEvent, AsyncLogger, Event, Logger, Metadata, StandardFilter, Writer,
+};
+
+pub struct SyslogLogger {
+    pub(crate) printer: SyslogWriter,
+    pub(crate) filter: StandardFilter,
+    pub(crate) formatter: SyslogFormatter,
+}
+
+impl Logger for SyslogLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+
+    fn record(&self, event: &Event) {
+        let s = Formatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+
+    fn flush(&self) {
+        self.printer.flush()
+    }
+}
+
+impl AsyncLogger for SyslogLogger {
+    fn record(&self, event: &AsyncEvent) {
+        let s = AsyncFormatter::format(&self.formatter, event).expect("Unable to format");
+        self.printer.write(s);
+    }
+}
@@ -11,6 +11,13 @@ pub struct TcpLogger {
     pub(crate) formatter: JsonFormatter,
 }
 
+impl TcpLogger {
+    /// Number of logs which could neither be sent nor kept in the backlog.
+    pub fn dropped_events(&self) -> u64 {
+        self.printer.dropped_events()
+    }
+}
+
 impl Logger for TcpLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
         self.filter.enabled(metadata)
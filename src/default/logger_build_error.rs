@@ -0,0 +1,44 @@
+use std::{error, fmt, io};
+
+/// Reasons why [`DefaultLoggerBuilder::try_build`](super::DefaultLoggerBuilder::try_build) failed.
+#[derive(Debug)]
+pub enum LoggerBuildError {
+    /// A log file, a certificate or a private key could not be opened.
+    Io(io::Error),
+    /// An endpoint such as the airbrake host is not a valid address.
+    InvalidEndpoint(String),
+    /// The combination of options is not supported, e.g. a tcp logger in sync mode.
+    UnsupportedConfiguration(String),
+    /// Another logger has already been installed as the global logger.
+    GlobalLoggerAlreadySet,
+}
+
+impl fmt::Display for LoggerBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoggerBuildError::Io(err) => write!(f, "I/O error: {}", err),
+            LoggerBuildError::InvalidEndpoint(reason) => write!(f, "Invalid endpoint: {}", reason),
+            LoggerBuildError::UnsupportedConfiguration(reason) => {
+                write!(f, "Unsupported configuration: {}", reason)
+            }
+            LoggerBuildError::GlobalLoggerAlreadySet => {
+                write!(f, "Global logger has already been set")
+            }
+        }
+    }
+}
+
+impl error::Error for LoggerBuildError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            LoggerBuildError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoggerBuildError {
+    fn from(err: io::Error) -> Self {
+        LoggerBuildError::Io(err)
+    }
+}
@@ -1,28 +1,79 @@
 use super::*;
 #[cfg(any(feature = "airbrake"))]
 use crate::default::logger::AirbrakeLogger;
+#[cfg(any(feature = "fluentd"))]
+use crate::default::logger::FluentdLogger;
+#[cfg(any(feature = "gelf"))]
+use crate::default::logger::GelfLogger;
+#[cfg(all(target_os = "linux", feature = "journald"))]
+use crate::default::logger::JournaldLogger;
+#[cfg(any(feature = "sentry"))]
+use crate::default::logger::SentryLogger;
+#[cfg(any(feature = "syslog"))]
+use crate::default::logger::SyslogLogger;
 #[cfg(any(feature = "tcp"))]
 use crate::default::logger::TcpLogger;
+#[cfg(any(feature = "elasticsearch"))]
+use crate::default::logger::{ElasticsearchLogger, ELASTICSEARCH_BULK_PATH};
 use crate::default::logger::{LocalFileLogger, SyncLoggerImpl};
+#[cfg(any(feature = "loki"))]
+use crate::default::logger::{LokiLogger, LOKI_PUSH_PATH};
+#[cfg(any(feature = "otlp"))]
+use crate::default::logger::{OtlpLogger, OTLP_LOGS_PATH};
+#[cfg(any(feature = "splunk"))]
+use crate::default::logger::{SplunkLogger, SPLUNK_EVENT_PATH};
+use crate::default::logger_build_error::LoggerBuildError;
 use crate::default::logger_service::DefaultLoggerService;
 use crate::formatter::*;
+#[cfg(any(feature = "tls"))]
+use crate::writer::TcpTlsConfig;
 #[cfg(any(feature = "tcp"))]
-use crate::writer::TcpWriter;
-use crate::{writer::*, Level, StandardFilterBuilder};
+use crate::writer::{BacklogPolicy, ReconnectPolicy, TcpFraming, TcpWriter};
+use crate::{
+    writer::*, ArchivePolicy, FileDurability, FileRotation, Level, StandardFilter,
+    StandardFilterBuilder,
+};
+#[cfg(any(feature = "loki"))]
+use std::collections::BTreeMap;
 use std::{
     env, path,
     sync::{mpsc, Arc},
     thread,
+    time::Duration,
 };
 
 const RUST_LOG: &str = "RUST_LOG";
 #[cfg(any(feature = "tcp"))]
 const RUST_TCP_LOG: &str = "RUST_TCP_LOG";
+#[cfg(any(feature = "loki"))]
+const RUST_LOKI_LOG: &str = "RUST_LOKI_LOG";
+#[cfg(any(feature = "elasticsearch"))]
+const RUST_ELASTICSEARCH_LOG: &str = "RUST_ELASTICSEARCH_LOG";
+#[cfg(any(feature = "splunk"))]
+const RUST_SPLUNK_LOG: &str = "RUST_SPLUNK_LOG";
+#[cfg(any(feature = "otlp"))]
+const RUST_OTLP_LOG: &str = "RUST_OTLP_LOG";
+#[cfg(any(feature = "syslog"))]
+const RUST_SYSLOG_LOG: &str = "RUST_SYSLOG_LOG";
+#[cfg(all(target_os = "linux", feature = "journald"))]
+const RUST_JOURNALD_LOG: &str = "RUST_JOURNALD_LOG";
+#[cfg(any(feature = "gelf"))]
+const RUST_GELF_LOG: &str = "RUST_GELF_LOG";
+#[cfg(any(feature = "fluentd"))]
+const RUST_FLUENTD_LOG: &str = "RUST_FLUENTD_LOG";
 /// Default size of log write channel, if the channel is full, logs will be dropped
 pub const CHANNEL_SIZE: usize = 10000;
 
 pub struct DefaultLoggerBuilder {
     file: Option<path::PathBuf>,
+    file_rotation: FileRotation,
+    file_archive: ArchivePolicy,
+    #[cfg(all(unix, feature = "sighup"))]
+    file_reopen_on_sighup: bool,
+    file_check_path_interval: Option<Duration>,
+    file_buffer: Option<(usize, Duration)>,
+    file_sync_on_flush: bool,
+    file_durability: FileDurability,
     level: Level,
     is_async: bool,
     channel_size: usize,
@@ -30,6 +81,16 @@ pub struct DefaultLoggerBuilder {
     tcp_level: Level,
     #[cfg(any(feature = "tcp"))]
     tcp_address: Option<String>,
+    #[cfg(any(feature = "tcp"))]
+    tcp_framing: TcpFraming,
+    #[cfg(any(feature = "tcp"))]
+    tcp_reconnect: ReconnectPolicy,
+    #[cfg(any(feature = "tcp"))]
+    tcp_timeouts: Option<(Duration, Duration)>,
+    #[cfg(any(feature = "tcp"))]
+    tcp_backlog: Option<BacklogPolicy>,
+    #[cfg(any(feature = "tls"))]
+    tcp_tls: Option<TcpTlsConfig>,
     #[cfg(any(feature = "airbrake"))]
     airbrake_host: Option<String>,
     #[cfg(any(feature = "airbrake"))]
@@ -38,6 +99,88 @@ pub struct DefaultLoggerBuilder {
     airbrake_project_key: Option<String>,
     #[cfg(any(feature = "airbrake"))]
     airbrake_environment: Option<String>,
+    #[cfg(any(feature = "loki"))]
+    loki_level: Level,
+    #[cfg(any(feature = "loki"))]
+    loki_url: Option<String>,
+    #[cfg(any(feature = "loki"))]
+    loki_labels: BTreeMap<String, String>,
+    #[cfg(any(feature = "loki"))]
+    loki_batch: BatchPolicy,
+    #[cfg(any(feature = "elasticsearch"))]
+    elasticsearch_level: Level,
+    #[cfg(any(feature = "elasticsearch"))]
+    elasticsearch_url: Option<String>,
+    #[cfg(any(feature = "elasticsearch"))]
+    elasticsearch_index: String,
+    #[cfg(any(feature = "elasticsearch"))]
+    elasticsearch_batch: BatchPolicy,
+    #[cfg(any(feature = "splunk"))]
+    splunk_level: Level,
+    #[cfg(any(feature = "splunk"))]
+    splunk_url: Option<String>,
+    #[cfg(any(feature = "splunk"))]
+    splunk_token: Option<String>,
+    #[cfg(any(feature = "splunk"))]
+    splunk_source: Option<String>,
+    #[cfg(any(feature = "splunk"))]
+    splunk_sourcetype: Option<String>,
+    #[cfg(any(feature = "splunk"))]
+    splunk_index: Option<String>,
+    #[cfg(any(feature = "splunk"))]
+    splunk_batch: BatchPolicy,
+    #[cfg(any(feature = "sentry"))]
+    sentry_dsn: Option<String>,
+    #[cfg(any(feature = "sentry"))]
+    sentry_environment: Option<String>,
+    #[cfg(any(feature = "sentry"))]
+    sentry_release: Option<String>,
+    #[cfg(any(feature = "otlp"))]
+    otlp_level: Level,
+    #[cfg(any(feature = "otlp"))]
+    otlp_url: Option<String>,
+    #[cfg(any(feature = "otlp"))]
+    otlp_service_name: Option<String>,
+    #[cfg(any(feature = "otlp"))]
+    otlp_protocol: OtlpProtocol,
+    #[cfg(any(feature = "otlp"))]
+    otlp_batch: BatchPolicy,
+    #[cfg(any(feature = "syslog"))]
+    syslog_level: Level,
+    #[cfg(any(feature = "syslog"))]
+    syslog_transport: Option<SyslogTransport>,
+    #[cfg(any(feature = "syslog"))]
+    syslog_format: SyslogFormat,
+    #[cfg(any(feature = "syslog"))]
+    syslog_facility: SyslogFacility,
+    #[cfg(any(feature = "syslog"))]
+    syslog_app_name: Option<String>,
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    journald_level: Level,
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    journald: bool,
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    journald_identifier: Option<String>,
+    #[cfg(any(feature = "gelf"))]
+    gelf_level: Level,
+    #[cfg(any(feature = "gelf"))]
+    gelf_transport: Option<GelfTransport>,
+    #[cfg(any(feature = "gelf"))]
+    gelf_compression: Option<GelfCompression>,
+    #[cfg(any(feature = "gelf"))]
+    gelf_reconnect: ReconnectPolicy,
+    #[cfg(any(feature = "gelf"))]
+    gelf_timeouts: Option<(Duration, Duration)>,
+    #[cfg(any(feature = "fluentd"))]
+    fluentd_level: Level,
+    #[cfg(any(feature = "fluentd"))]
+    fluentd_address: Option<String>,
+    #[cfg(any(feature = "fluentd"))]
+    fluentd_tag: String,
+    #[cfg(any(feature = "fluentd"))]
+    fluentd_reconnect: ReconnectPolicy,
+    #[cfg(any(feature = "fluentd"))]
+    fluentd_ack_timeout: Option<Duration>,
 }
 
 impl DefaultLoggerBuilder {
@@ -45,6 +188,14 @@ impl DefaultLoggerBuilder {
     pub fn new() -> Self {
         Self {
             file: None, // default print to console
+            file_rotation: FileRotation::Never,
+            file_archive: ArchivePolicy::default(),
+            #[cfg(all(unix, feature = "sighup"))]
+            file_reopen_on_sighup: false,
+            file_check_path_interval: None,
+            file_buffer: None,
+            file_sync_on_flush: false,
+            file_durability: FileDurability::None,
             level: Level::Info,
             is_async: false,
             channel_size: CHANNEL_SIZE,
@@ -52,6 +203,16 @@ impl DefaultLoggerBuilder {
             tcp_level: Level::Info,
             #[cfg(any(feature = "tcp"))]
             tcp_address: None,
+            #[cfg(any(feature = "tcp"))]
+            tcp_framing: TcpFraming::NewlineDelimited,
+            #[cfg(any(feature = "tcp"))]
+            tcp_reconnect: ReconnectPolicy::default(),
+            #[cfg(any(feature = "tcp"))]
+            tcp_timeouts: None,
+            #[cfg(any(feature = "tcp"))]
+            tcp_backlog: None,
+            #[cfg(any(feature = "tls"))]
+            tcp_tls: None,
             #[cfg(any(feature = "airbrake"))]
             airbrake_host: None,
             #[cfg(any(feature = "airbrake"))]
@@ -60,6 +221,88 @@ impl DefaultLoggerBuilder {
             airbrake_project_key: None,
             #[cfg(any(feature = "airbrake"))]
             airbrake_environment: None,
+            #[cfg(any(feature = "loki"))]
+            loki_level: Level::Info,
+            #[cfg(any(feature = "loki"))]
+            loki_url: None,
+            #[cfg(any(feature = "loki"))]
+            loki_labels: BTreeMap::new(),
+            #[cfg(any(feature = "loki"))]
+            loki_batch: BatchPolicy::default(),
+            #[cfg(any(feature = "elasticsearch"))]
+            elasticsearch_level: Level::Info,
+            #[cfg(any(feature = "elasticsearch"))]
+            elasticsearch_url: None,
+            #[cfg(any(feature = "elasticsearch"))]
+            elasticsearch_index: "logs-%Y.%m.%d".to_owned(),
+            #[cfg(any(feature = "elasticsearch"))]
+            elasticsearch_batch: BatchPolicy::default(),
+            #[cfg(any(feature = "splunk"))]
+            splunk_level: Level::Info,
+            #[cfg(any(feature = "splunk"))]
+            splunk_url: None,
+            #[cfg(any(feature = "splunk"))]
+            splunk_token: None,
+            #[cfg(any(feature = "splunk"))]
+            splunk_source: None,
+            #[cfg(any(feature = "splunk"))]
+            splunk_sourcetype: None,
+            #[cfg(any(feature = "splunk"))]
+            splunk_index: None,
+            #[cfg(any(feature = "splunk"))]
+            splunk_batch: BatchPolicy::default(),
+            #[cfg(any(feature = "sentry"))]
+            sentry_dsn: None,
+            #[cfg(any(feature = "sentry"))]
+            sentry_environment: None,
+            #[cfg(any(feature = "sentry"))]
+            sentry_release: None,
+            #[cfg(any(feature = "otlp"))]
+            otlp_level: Level::Info,
+            #[cfg(any(feature = "otlp"))]
+            otlp_url: None,
+            #[cfg(any(feature = "otlp"))]
+            otlp_service_name: None,
+            #[cfg(any(feature = "otlp"))]
+            otlp_protocol: OtlpProtocol::HttpJson,
+            #[cfg(any(feature = "otlp"))]
+            otlp_batch: BatchPolicy::default(),
+            #[cfg(any(feature = "syslog"))]
+            syslog_level: Level::Info,
+            #[cfg(any(feature = "syslog"))]
+            syslog_transport: None,
+            #[cfg(any(feature = "syslog"))]
+            syslog_format: SyslogFormat::Rfc5424,
+            #[cfg(any(feature = "syslog"))]
+            syslog_facility: SyslogFacility::User,
+            #[cfg(any(feature = "syslog"))]
+            syslog_app_name: None,
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            journald_level: Level::Info,
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            journald: false,
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            journald_identifier: None,
+            #[cfg(any(feature = "gelf"))]
+            gelf_level: Level::Info,
+            #[cfg(any(feature = "gelf"))]
+            gelf_transport: None,
+            #[cfg(any(feature = "gelf"))]
+            gelf_compression: None,
+            #[cfg(any(feature = "gelf"))]
+            gelf_reconnect: ReconnectPolicy::default(),
+            #[cfg(any(feature = "gelf"))]
+            gelf_timeouts: None,
+            #[cfg(any(feature = "fluentd"))]
+            fluentd_level: Level::Info,
+            #[cfg(any(feature = "fluentd"))]
+            fluentd_address: None,
+            #[cfg(any(feature = "fluentd"))]
+            fluentd_tag: "app.{level}".to_owned(),
+            #[cfg(any(feature = "fluentd"))]
+            fluentd_reconnect: ReconnectPolicy::default(),
+            #[cfg(any(feature = "fluentd"))]
+            fluentd_ack_timeout: None,
         }
     }
 
@@ -68,6 +311,66 @@ impl DefaultLoggerBuilder {
         self
     }
 
+    /// See [`FileRotation`] for the placeholders `file` may contain.
+    pub fn file_rotation(&mut self, file_rotation: FileRotation) -> &mut Self {
+        self.file_rotation = file_rotation;
+        self
+    }
+
+    /// Compress and prune the files rotated out of `file`.
+    pub fn file_archive(&mut self, file_archive: ArchivePolicy) -> &mut Self {
+        self.file_archive = file_archive;
+        self
+    }
+
+    /// Reopen `file` on SIGHUP, for use with an external logrotate.
+    #[cfg(all(unix, feature = "sighup"))]
+    pub fn file_reopen_on_sighup(&mut self, file_reopen_on_sighup: bool) -> &mut Self {
+        self.file_reopen_on_sighup = file_reopen_on_sighup;
+        self
+    }
+
+    /// Periodically check whether `file` has been moved away and reopen it if so.
+    pub fn file_check_path_interval(&mut self, file_check_path_interval: Duration) -> &mut Self {
+        self.file_check_path_interval = Some(file_check_path_interval);
+        self
+    }
+
+    /// Buffer up to `capacity` bytes of `file` output in memory, flushing at the latest
+    /// after `max_flush_interval` and whenever [`crate::flush`] is called.
+    pub fn file_buffer(&mut self, capacity: usize, max_flush_interval: Duration) -> &mut Self {
+        self.file_buffer = Some((capacity, max_flush_interval));
+        self
+    }
+
+    /// `fsync` `file` whenever the logger is flushed.
+    pub fn file_sync_on_flush(&mut self, file_sync_on_flush: bool) -> &mut Self {
+        self.file_sync_on_flush = file_sync_on_flush;
+        self
+    }
+
+    /// Make each event recorded to `file` durable before `record` returns. Only the
+    /// synchronous mode blocks the caller, the async mode blocks the logger service thread.
+    pub fn file_durability(&mut self, file_durability: FileDurability) -> &mut Self {
+        self.file_durability = file_durability;
+        self
+    }
+
+    /// Log to `file`, rolling it over to `file.1 .. file.<max_files>` once it reaches `max_bytes`.
+    pub fn rotating_file<T: Into<path::PathBuf>>(
+        &mut self,
+        file: T,
+        max_bytes: u64,
+        max_files: usize,
+    ) -> &mut Self {
+        self.file = Some(file.into());
+        self.file_rotation = FileRotation::Size {
+            max_bytes,
+            max_files,
+        };
+        self
+    }
+
     pub fn level(&mut self, level: Level) -> &mut Self {
         self.level = level;
         self
@@ -96,6 +399,43 @@ impl DefaultLoggerBuilder {
         self
     }
 
+    #[cfg(any(feature = "tcp"))]
+    pub fn tcp_framing(&mut self, tcp_framing: TcpFraming) -> &mut Self {
+        self.tcp_framing = tcp_framing;
+        self
+    }
+
+    #[cfg(any(feature = "tcp"))]
+    pub fn tcp_reconnect(&mut self, tcp_reconnect: ReconnectPolicy) -> &mut Self {
+        self.tcp_reconnect = tcp_reconnect;
+        self
+    }
+
+    /// Timeouts for connecting to the tcp endpoint and for sending a single log.
+    #[cfg(any(feature = "tcp"))]
+    pub fn tcp_timeouts(
+        &mut self,
+        connect_timeout: Duration,
+        write_timeout: Duration,
+    ) -> &mut Self {
+        self.tcp_timeouts = Some((connect_timeout, write_timeout));
+        self
+    }
+
+    /// Keep tcp logs while the endpoint is unreachable, see [`BacklogPolicy`].
+    #[cfg(any(feature = "tcp"))]
+    pub fn tcp_backlog(&mut self, tcp_backlog: BacklogPolicy) -> &mut Self {
+        self.tcp_backlog = Some(tcp_backlog);
+        self
+    }
+
+    /// Ship tcp logs over TLS instead of plaintext.
+    #[cfg(any(feature = "tls"))]
+    pub fn tcp_tls(&mut self, tcp_tls: TcpTlsConfig) -> &mut Self {
+        self.tcp_tls = Some(tcp_tls);
+        self
+    }
+
     #[cfg(any(feature = "airbrake"))]
     pub fn airbrake_host(&mut self, airbrake_host: String) -> &mut Self {
         self.airbrake_host = Some(airbrake_host);
@@ -122,42 +462,555 @@ impl DefaultLoggerBuilder {
 
     #[cfg(any(feature = "airbrake"))]
     pub fn airbrake_endpoint(&self) -> Option<String> {
-        if self.airbrake_host.is_none() {
-            return None;
-        }
-        if self.airbrake_project_id.is_none() {
-            return None;
-        }
-        if self.airbrake_project_key.is_none() {
-            return None;
-        }
+        let airbrake_host = self.airbrake_host.as_ref()?;
+        let airbrake_project_id = self.airbrake_project_id.as_ref()?;
+        let airbrake_project_key = self.airbrake_project_key.as_ref()?;
         let url = format!(
             "{}/api/v3/projects/{}/notices?key={}",
-            self.airbrake_host.as_ref().unwrap(),
-            self.airbrake_project_id.as_ref().unwrap(),
-            self.airbrake_project_key.as_ref().unwrap(),
+            airbrake_host, airbrake_project_id, airbrake_project_key,
         );
         Some(url)
     }
 
-    pub fn build(&mut self) -> Arc<DefaultLogger> {
-        let filter = {
-            let mut filter_builder = StandardFilterBuilder::new();
-            if env::var(RUST_LOG).is_ok() {
-                filter_builder.with_env(RUST_LOG);
-            } else {
-                filter_builder.filter_level(self.level.into());
+    #[cfg(any(feature = "loki"))]
+    pub fn loki_level(&mut self, loki_level: Level) -> &mut Self {
+        self.loki_level = loki_level;
+        self
+    }
+
+    /// Base url of the Loki server, e.g. `http://localhost:3100`.
+    #[cfg(any(feature = "loki"))]
+    pub fn loki_url(&mut self, loki_url: String) -> &mut Self {
+        self.loki_url = Some(loki_url);
+        self
+    }
+
+    /// Add a static label to every stream, next to `level`, `target` and `hostname`.
+    #[cfg(any(feature = "loki"))]
+    pub fn loki_label(&mut self, name: String, value: String) -> &mut Self {
+        self.loki_labels.insert(name, value);
+        self
+    }
+
+    #[cfg(any(feature = "loki"))]
+    pub fn loki_batch(&mut self, loki_batch: BatchPolicy) -> &mut Self {
+        self.loki_batch = loki_batch;
+        self
+    }
+
+    #[cfg(any(feature = "elasticsearch"))]
+    pub fn elasticsearch_level(&mut self, elasticsearch_level: Level) -> &mut Self {
+        self.elasticsearch_level = elasticsearch_level;
+        self
+    }
+
+    /// Base url of the Elasticsearch or OpenSearch cluster, e.g. `http://localhost:9200`.
+    #[cfg(any(feature = "elasticsearch"))]
+    pub fn elasticsearch_url(&mut self, elasticsearch_url: String) -> &mut Self {
+        self.elasticsearch_url = Some(elasticsearch_url);
+        self
+    }
+
+    /// Name of the index events are written to, expanded with the event timestamp
+    /// as in [`chrono::format::strftime`]. `logs-%Y.%m.%d` by default.
+    #[cfg(any(feature = "elasticsearch"))]
+    pub fn elasticsearch_index(&mut self, elasticsearch_index: String) -> &mut Self {
+        self.elasticsearch_index = elasticsearch_index;
+        self
+    }
+
+    #[cfg(any(feature = "elasticsearch"))]
+    pub fn elasticsearch_batch(&mut self, elasticsearch_batch: BatchPolicy) -> &mut Self {
+        self.elasticsearch_batch = elasticsearch_batch;
+        self
+    }
+
+    #[cfg(any(feature = "splunk"))]
+    pub fn splunk_level(&mut self, splunk_level: Level) -> &mut Self {
+        self.splunk_level = splunk_level;
+        self
+    }
+
+    /// Base url of the HTTP Event Collector, e.g. `https://splunk.example.com:8088`.
+    /// Events are only sent once the token is set as well.
+    #[cfg(any(feature = "splunk"))]
+    pub fn splunk_url(&mut self, splunk_url: String) -> &mut Self {
+        self.splunk_url = Some(splunk_url);
+        self
+    }
+
+    #[cfg(any(feature = "splunk"))]
+    pub fn splunk_token(&mut self, splunk_token: String) -> &mut Self {
+        self.splunk_token = Some(splunk_token);
+        self
+    }
+
+    #[cfg(any(feature = "splunk"))]
+    pub fn splunk_source(&mut self, splunk_source: String) -> &mut Self {
+        self.splunk_source = Some(splunk_source);
+        self
+    }
+
+    #[cfg(any(feature = "splunk"))]
+    pub fn splunk_sourcetype(&mut self, splunk_sourcetype: String) -> &mut Self {
+        self.splunk_sourcetype = Some(splunk_sourcetype);
+        self
+    }
+
+    #[cfg(any(feature = "splunk"))]
+    pub fn splunk_index(&mut self, splunk_index: String) -> &mut Self {
+        self.splunk_index = Some(splunk_index);
+        self
+    }
+
+    #[cfg(any(feature = "splunk"))]
+    pub fn splunk_batch(&mut self, splunk_batch: BatchPolicy) -> &mut Self {
+        self.splunk_batch = splunk_batch;
+        self
+    }
+
+    /// The DSN of the Sentry project, e.g. `https://<key>@o0.ingest.sentry.io/<project id>`.
+    #[cfg(any(feature = "sentry"))]
+    pub fn sentry_dsn(&mut self, sentry_dsn: String) -> &mut Self {
+        self.sentry_dsn = Some(sentry_dsn);
+        self
+    }
+
+    #[cfg(any(feature = "sentry"))]
+    pub fn sentry_environment(&mut self, sentry_environment: String) -> &mut Self {
+        self.sentry_environment = Some(sentry_environment);
+        self
+    }
+
+    #[cfg(any(feature = "sentry"))]
+    pub fn sentry_release(&mut self, sentry_release: String) -> &mut Self {
+        self.sentry_release = Some(sentry_release);
+        self
+    }
+
+    #[cfg(any(feature = "otlp"))]
+    pub fn otlp_level(&mut self, otlp_level: Level) -> &mut Self {
+        self.otlp_level = otlp_level;
+        self
+    }
+
+    /// Base url of the OTLP/HTTP collector, e.g. `http://localhost:4318`.
+    #[cfg(any(feature = "otlp"))]
+    pub fn otlp_url(&mut self, otlp_url: String) -> &mut Self {
+        self.otlp_url = Some(otlp_url);
+        self
+    }
+
+    /// The `service.name` resource attribute, `unknown_service:<executable name>` by default.
+    #[cfg(any(feature = "otlp"))]
+    pub fn otlp_service_name(&mut self, otlp_service_name: String) -> &mut Self {
+        self.otlp_service_name = Some(otlp_service_name);
+        self
+    }
+
+    #[cfg(any(feature = "otlp"))]
+    pub fn otlp_protocol(&mut self, otlp_protocol: OtlpProtocol) -> &mut Self {
+        self.otlp_protocol = otlp_protocol;
+        self
+    }
+
+    #[cfg(any(feature = "otlp"))]
+    pub fn otlp_batch(&mut self, otlp_batch: BatchPolicy) -> &mut Self {
+        self.otlp_batch = otlp_batch;
+        self
+    }
+
+    #[cfg(any(feature = "syslog"))]
+    pub fn syslog_level(&mut self, syslog_level: Level) -> &mut Self {
+        self.syslog_level = syslog_level;
+        self
+    }
+
+    /// Send logs to syslog, e.g. [`SyslogTransport::local`]. A tcp transport is only
+    /// supported in async mode.
+    #[cfg(any(feature = "syslog"))]
+    pub fn syslog_transport(&mut self, syslog_transport: SyslogTransport) -> &mut Self {
+        self.syslog_transport = Some(syslog_transport);
+        self
+    }
+
+    #[cfg(any(feature = "syslog"))]
+    pub fn syslog_format(&mut self, syslog_format: SyslogFormat) -> &mut Self {
+        self.syslog_format = syslog_format;
+        self
+    }
+
+    #[cfg(any(feature = "syslog"))]
+    pub fn syslog_facility(&mut self, syslog_facility: SyslogFacility) -> &mut Self {
+        self.syslog_facility = syslog_facility;
+        self
+    }
+
+    /// APP-NAME or TAG of the messages, the executable name by default.
+    #[cfg(any(feature = "syslog"))]
+    pub fn syslog_app_name(&mut self, syslog_app_name: String) -> &mut Self {
+        self.syslog_app_name = Some(syslog_app_name);
+        self
+    }
+
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    pub fn journald_level(&mut self, journald_level: Level) -> &mut Self {
+        self.journald_level = journald_level;
+        self
+    }
+
+    /// Send logs to the systemd journal through its native protocol.
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    pub fn journald(&mut self, journald: bool) -> &mut Self {
+        self.journald = journald;
+        self
+    }
+
+    /// `SYSLOG_IDENTIFIER` of the entries, the executable name by default.
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    pub fn journald_identifier(&mut self, journald_identifier: String) -> &mut Self {
+        self.journald_identifier = Some(journald_identifier);
+        self
+    }
+
+    #[cfg(any(feature = "gelf"))]
+    pub fn gelf_level(&mut self, gelf_level: Level) -> &mut Self {
+        self.gelf_level = gelf_level;
+        self
+    }
+
+    /// Send logs to Graylog as GELF. A tcp transport is only supported in async mode.
+    #[cfg(any(feature = "gelf"))]
+    pub fn gelf_transport(&mut self, gelf_transport: GelfTransport) -> &mut Self {
+        self.gelf_transport = Some(gelf_transport);
+        self
+    }
+
+    /// Compress GELF messages sent over udp.
+    #[cfg(any(feature = "gelf"))]
+    pub fn gelf_compression(&mut self, gelf_compression: GelfCompression) -> &mut Self {
+        self.gelf_compression = Some(gelf_compression);
+        self
+    }
+
+    /// Back off between connection attempts of a tcp transport, see [`ReconnectPolicy`].
+    #[cfg(any(feature = "gelf"))]
+    pub fn gelf_reconnect(&mut self, gelf_reconnect: ReconnectPolicy) -> &mut Self {
+        self.gelf_reconnect = gelf_reconnect;
+        self
+    }
+
+    /// Timeouts for connecting to a tcp transport and for sending a single message.
+    #[cfg(any(feature = "gelf"))]
+    pub fn gelf_timeouts(
+        &mut self,
+        connect_timeout: Duration,
+        write_timeout: Duration,
+    ) -> &mut Self {
+        self.gelf_timeouts = Some((connect_timeout, write_timeout));
+        self
+    }
+
+    #[cfg(any(feature = "fluentd"))]
+    pub fn fluentd_level(&mut self, fluentd_level: Level) -> &mut Self {
+        self.fluentd_level = fluentd_level;
+        self
+    }
+
+    /// Send logs to the Forward input of Fluentd or Fluent Bit at `host:port`, usually
+    /// port 24224. Only supported in async mode.
+    #[cfg(any(feature = "fluentd"))]
+    pub fn fluentd_address(&mut self, fluentd_address: String) -> &mut Self {
+        self.fluentd_address = Some(fluentd_address);
+        self
+    }
+
+    /// Template of the tag of the entries, `app.{level}` by default, see
+    /// [`FluentdFormatter`].
+    #[cfg(any(feature = "fluentd"))]
+    pub fn fluentd_tag(&mut self, fluentd_tag: String) -> &mut Self {
+        self.fluentd_tag = fluentd_tag;
+        self
+    }
+
+    #[cfg(any(feature = "fluentd"))]
+    pub fn fluentd_reconnect(&mut self, fluentd_reconnect: ReconnectPolicy) -> &mut Self {
+        self.fluentd_reconnect = fluentd_reconnect;
+        self
+    }
+
+    /// Have every message acknowledged, see [`FluentdWriterBuilder::require_ack`].
+    #[cfg(any(feature = "fluentd"))]
+    pub fn fluentd_require_ack(&mut self, fluentd_ack_timeout: Duration) -> &mut Self {
+        self.fluentd_ack_timeout = Some(fluentd_ack_timeout);
+        self
+    }
+
+    /// Filter by `level`, unless the `env_key` environment variable holds directives.
+    fn level_filter(env_key: &str, level: Level) -> StandardFilter {
+        let mut filter_builder = StandardFilterBuilder::new();
+        if env::var(env_key).is_ok() {
+            filter_builder.with_env(env_key);
+        } else {
+            filter_builder.filter_level(level.into());
+        }
+        filter_builder.build()
+    }
+
+    #[cfg(any(feature = "loki"))]
+    fn loki_logger(&self, loki_url: &str) -> Result<LokiLogger, LoggerBuildError> {
+        let push_url = format!("{}{}", loki_url.trim_end_matches('/'), LOKI_PUSH_PATH);
+        let printer = HttpWriter::builder(push_url)
+            .map_err(|err| LoggerBuildError::InvalidEndpoint(err.to_string()))?
+            .batch(self.loki_batch)
+            .encoder(LokiEncoder)
+            .retry(RetryPolicy::exponential())
+            .build();
+        Ok(LokiLogger {
+            printer,
+            filter: Self::level_filter(RUST_LOKI_LOG, self.loki_level),
+            formatter: LokiFormatter::new(self.loki_labels.clone()),
+        })
+    }
+
+    #[cfg(any(feature = "elasticsearch"))]
+    fn elasticsearch_logger(
+        &self,
+        elasticsearch_url: &str,
+    ) -> Result<ElasticsearchLogger, LoggerBuildError> {
+        let formatter = ElasticsearchFormatter::new(self.elasticsearch_index.clone())
+            .map_err(|err| LoggerBuildError::UnsupportedConfiguration(err.to_string()))?;
+        let bulk_url = format!(
+            "{}{}",
+            elasticsearch_url.trim_end_matches('/'),
+            ELASTICSEARCH_BULK_PATH
+        );
+        let printer = HttpWriter::builder(bulk_url)
+            .map_err(|err| LoggerBuildError::InvalidEndpoint(err.to_string()))?
+            .batch(self.elasticsearch_batch)
+            .encoder(ElasticsearchEncoder)
+            .retry(RetryPolicy::exponential())
+            .build();
+        Ok(ElasticsearchLogger {
+            printer,
+            filter: Self::level_filter(RUST_ELASTICSEARCH_LOG, self.elasticsearch_level),
+            formatter,
+        })
+    }
+
+    #[cfg(any(feature = "splunk"))]
+    fn splunk_logger(
+        &self,
+        splunk_url: &str,
+        splunk_token: &str,
+    ) -> Result<SplunkLogger, LoggerBuildError> {
+        let event_url = format!("{}{}", splunk_url.trim_end_matches('/'), SPLUNK_EVENT_PATH);
+        let printer = HttpWriter::builder(event_url)
+            .map_err(|err| LoggerBuildError::InvalidEndpoint(err.to_string()))?
+            .header(
+                http::header::AUTHORIZATION,
+                format!("Splunk {}", splunk_token),
+            )
+            .batch(self.splunk_batch)
+            .retry(RetryPolicy::exponential())
+            .encoder(SplunkEncoder)
+            .try_build()
+            .map_err(|err| {
+                LoggerBuildError::UnsupportedConfiguration(format!("invalid splunk token: {}", err))
+            })?;
+        Ok(SplunkLogger {
+            printer,
+            filter: Self::level_filter(RUST_SPLUNK_LOG, self.splunk_level),
+            formatter: SplunkFormatter::new(
+                self.splunk_source.clone(),
+                self.splunk_sourcetype.clone(),
+                self.splunk_index.clone(),
+            ),
+        })
+    }
+
+    #[cfg(any(feature = "otlp"))]
+    fn otlp_logger(&self, otlp_url: &str) -> Result<OtlpLogger, LoggerBuildError> {
+        let logs_url = format!("{}{}", otlp_url.trim_end_matches('/'), OTLP_LOGS_PATH);
+        let printer = HttpWriter::builder(logs_url)
+            .map_err(|err| LoggerBuildError::InvalidEndpoint(err.to_string()))?
+            .batch(self.otlp_batch)
+            .encoder(self.otlp_protocol)
+            .retry(RetryPolicy::exponential())
+            .build();
+        let service_name = self
+            .otlp_service_name
+            .clone()
+            .unwrap_or_else(|| format!("unknown_service:{}", executable_name()));
+        Ok(OtlpLogger {
+            printer,
+            filter: Self::level_filter(RUST_OTLP_LOG, self.otlp_level),
+            formatter: OtlpFormatter::new(service_name),
+        })
+    }
+
+    #[cfg(any(feature = "syslog"))]
+    fn syslog_logger(
+        &self,
+        syslog_transport: &SyslogTransport,
+    ) -> Result<SyslogLogger, LoggerBuildError> {
+        let app_name = self.syslog_app_name.clone().unwrap_or_else(executable_name);
+        #[allow(unused_mut)]
+        let mut formatter =
+            SyslogFormatter::new(self.syslog_format, self.syslog_facility, app_name);
+        #[cfg(unix)]
+        if let SyslogTransport::Unix(_) = syslog_transport {
+            formatter = formatter.include_hostname(false);
+        }
+        Ok(SyslogLogger {
+            printer: SyslogWriter::new(syslog_transport)?,
+            filter: Self::level_filter(RUST_SYSLOG_LOG, self.syslog_level),
+            formatter,
+        })
+    }
+
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    fn journald_logger(&self) -> Result<JournaldLogger, LoggerBuildError> {
+        let identifier = self
+            .journald_identifier
+            .clone()
+            .unwrap_or_else(executable_name);
+        Ok(JournaldLogger {
+            printer: JournaldWriter::new()?,
+            filter: Self::level_filter(RUST_JOURNALD_LOG, self.journald_level),
+            formatter: JournaldFormatter::new(identifier),
+        })
+    }
+
+    #[cfg(any(feature = "gelf"))]
+    fn gelf_logger(&self, gelf_transport: &GelfTransport) -> Result<GelfLogger, LoggerBuildError> {
+        let mut printer = match gelf_transport {
+            GelfTransport::Tcp(endpoint) => {
+                let mut builder = TcpWriter::builder(endpoint.clone());
+                builder.reconnect(self.gelf_reconnect);
+                if let Some((connect_timeout, write_timeout)) = self.gelf_timeouts {
+                    builder
+                        .connect_timeout(connect_timeout)
+                        .write_timeout(write_timeout);
+                }
+                GelfWriter::with_tcp(&mut builder)?
             }
-            filter_builder.build()
+            GelfTransport::Udp(_) => GelfWriter::new(gelf_transport)?,
         };
-        let logger = if self.is_async {
+        if let Some(gelf_compression) = self.gelf_compression {
+            printer = printer.compression(gelf_compression);
+        }
+        Ok(GelfLogger {
+            printer,
+            filter: Self::level_filter(RUST_GELF_LOG, self.gelf_level),
+            formatter: GelfFormatter,
+        })
+    }
+
+    #[cfg(any(feature = "fluentd"))]
+    fn fluentd_logger(&self, fluentd_address: &str) -> Result<FluentdLogger, LoggerBuildError> {
+        if !FluentdFormatter::is_valid_template(&self.fluentd_tag) {
+            return Err(LoggerBuildError::UnsupportedConfiguration(format!(
+                "invalid fluentd tag template: {}",
+                self.fluentd_tag
+            )));
+        }
+        let mut builder = FluentdWriter::builder(fluentd_address.to_owned());
+        builder.reconnect(self.fluentd_reconnect);
+        if let Some(fluentd_ack_timeout) = self.fluentd_ack_timeout {
+            builder.require_ack(fluentd_ack_timeout);
+        }
+        Ok(FluentdLogger {
+            printer: builder.build(),
+            filter: Self::level_filter(RUST_FLUENTD_LOG, self.fluentd_level),
+            formatter: FluentdFormatter::new(self.fluentd_tag.clone()),
+        })
+    }
+
+    fn file_writer(&self, file_path: &path::Path) -> Result<FileWriter, LoggerBuildError> {
+        let mut builder = FileWriter::builder(file_path);
+        builder
+            .rotation(self.file_rotation)
+            .archive(self.file_archive.clone())
+            .sync_on_flush(self.file_sync_on_flush)
+            .durability(self.file_durability);
+        #[cfg(all(unix, feature = "sighup"))]
+        builder.reopen_on_sighup(self.file_reopen_on_sighup);
+        if let Some(interval) = self.file_check_path_interval {
+            builder.check_path_interval(interval);
+        }
+        if let Some((capacity, max_flush_interval)) = self.file_buffer {
+            builder.buffer(capacity, max_flush_interval);
+        }
+        Ok(builder.try_build()?)
+    }
+
+    #[cfg(any(feature = "tcp"))]
+    fn tcp_writer(&self, tcp_address: &str) -> Result<TcpWriter, LoggerBuildError> {
+        let mut builder = TcpWriter::builder(tcp_address.to_owned());
+        builder
+            .framing(self.tcp_framing)
+            .reconnect(self.tcp_reconnect);
+        if let Some((connect_timeout, write_timeout)) = self.tcp_timeouts {
+            builder
+                .connect_timeout(connect_timeout)
+                .write_timeout(write_timeout);
+        }
+        if let Some(tcp_backlog) = self.tcp_backlog.clone() {
+            builder.backlog(tcp_backlog);
+        }
+        #[cfg(any(feature = "tls"))]
+        if let Some(tcp_tls) = self.tcp_tls.clone() {
+            builder.tls(tcp_tls);
+        }
+        Ok(builder.try_build()?)
+    }
+
+    /// Build the logger and install it as the global logger.
+    ///
+    /// # Panics
+    ///
+    /// Panics on misconfiguration, use [`try_build`](Self::try_build) to handle it instead.
+    pub fn build(&mut self) -> Arc<DefaultLogger> {
+        let (logger, service) = self.build_logger().unwrap_or_else(|err| panic!("{}", err));
+        crate::set_global_logger(logger.clone());
+        if let Some(service) = service {
+            thread::spawn(move || service.run());
+        }
+        logger
+    }
+
+    /// Build the logger and install it as the global logger, reporting misconfiguration
+    /// and an already installed global logger as [`LoggerBuildError`].
+    pub fn try_build(&mut self) -> Result<Arc<DefaultLogger>, LoggerBuildError> {
+        if crate::LOGGER.get().is_some() {
+            return Err(LoggerBuildError::GlobalLoggerAlreadySet);
+        }
+        let (logger, service) = self.build_logger()?;
+        // the service thread only starts once the logger is installed, a logger losing
+        // the race is dropped along with its service.
+        crate::LOGGER
+            .set(logger.clone())
+            .map_err(|_| LoggerBuildError::GlobalLoggerAlreadySet)?;
+        if let Some(service) = service {
+            thread::spawn(move || service.run());
+        }
+        Ok(logger)
+    }
+
+    /// Build the logger, along with the service to run on its own thread in async mode.
+    fn build_logger(
+        &mut self,
+    ) -> Result<(Arc<DefaultLogger>, Option<DefaultLoggerService>), LoggerBuildError> {
+        let filter = Self::level_filter(RUST_LOG, self.level);
+        let logger_and_service = if self.is_async {
             let (sender, receiver) = mpsc::sync_channel(self.channel_size);
             let filters = vec![filter.clone()];
             let mut loggers = vec![];
             {
                 let logger = if let Some(file_path) = &self.file {
                     ServiceLoggerImpl::LocalFile(LocalFileLogger {
-                        printer: FileWriter::new(file_path.clone()),
+                        printer: self.file_writer(file_path)?,
                         filter,
                         formatter: StandardFormatter,
                     })
@@ -171,19 +1024,15 @@ impl DefaultLoggerBuilder {
                 loggers.push(logger)
             }
 
+            #[cfg(any(feature = "tcp"))]
+            let mut tcp_dropped_events = None;
             #[cfg(any(feature = "tcp"))]
             if let Some(tcp_address) = self.tcp_address.as_deref() {
-                let tcp_filter = {
-                    let mut filter_builder = StandardFilterBuilder::new();
-                    if env::var(RUST_TCP_LOG).is_ok() {
-                        filter_builder.with_env(RUST_TCP_LOG);
-                    } else {
-                        filter_builder.filter_level(self.tcp_level.into());
-                    }
-                    filter_builder.build()
-                };
+                let tcp_filter = Self::level_filter(RUST_TCP_LOG, self.tcp_level);
+                let printer = self.tcp_writer(tcp_address)?;
+                tcp_dropped_events = Some(printer.dropped_events_counter());
                 let logger = ServiceLoggerImpl::Tcp(TcpLogger {
-                    printer: TcpWriter::new(tcp_address.to_owned()),
+                    printer,
                     filter: tcp_filter,
                     formatter: JsonFormatter,
                 });
@@ -192,22 +1041,98 @@ impl DefaultLoggerBuilder {
             #[cfg(any(feature = "airbrake"))]
             if let Some(airbrake_endpoint) = self.airbrake_endpoint() {
                 let environment = self.airbrake_environment.clone();
-                let logger = ServiceLoggerImpl::Airbrake(AirbrakeLogger::new(
-                    airbrake_endpoint,
-                    environment,
-                ));
+                let logger = ServiceLoggerImpl::Airbrake(
+                    AirbrakeLogger::try_new(airbrake_endpoint, environment)
+                        .map_err(|err| LoggerBuildError::InvalidEndpoint(err.to_string()))?,
+                );
                 loggers.push(logger);
             }
-            let logger = LoggerServiceDispatcher { filters, sender };
+            #[cfg(any(feature = "loki"))]
+            if let Some(loki_url) = self.loki_url.as_deref() {
+                loggers.push(ServiceLoggerImpl::Loki(self.loki_logger(loki_url)?));
+            }
+            #[cfg(any(feature = "elasticsearch"))]
+            if let Some(elasticsearch_url) = self.elasticsearch_url.as_deref() {
+                let logger = self.elasticsearch_logger(elasticsearch_url)?;
+                loggers.push(ServiceLoggerImpl::Elasticsearch(logger));
+            }
+            #[cfg(any(feature = "splunk"))]
+            if let (Some(splunk_url), Some(splunk_token)) =
+                (self.splunk_url.as_deref(), self.splunk_token.as_deref())
+            {
+                let logger = self.splunk_logger(splunk_url, splunk_token)?;
+                loggers.push(ServiceLoggerImpl::Splunk(logger));
+            }
+            #[cfg(any(feature = "sentry"))]
+            if let Some(sentry_dsn) = self.sentry_dsn.as_deref() {
+                let logger = SentryLogger::try_new(
+                    sentry_dsn,
+                    self.sentry_environment.clone(),
+                    self.sentry_release.clone(),
+                )
+                .map_err(|err| LoggerBuildError::InvalidEndpoint(err.to_string()))?;
+                loggers.push(ServiceLoggerImpl::Sentry(logger));
+            }
+            #[cfg(any(feature = "otlp"))]
+            if let Some(otlp_url) = self.otlp_url.as_deref() {
+                loggers.push(ServiceLoggerImpl::Otlp(self.otlp_logger(otlp_url)?));
+            }
+            #[cfg(any(feature = "syslog"))]
+            if let Some(syslog_transport) = self.syslog_transport.as_ref() {
+                let logger = self.syslog_logger(syslog_transport)?;
+                loggers.push(ServiceLoggerImpl::Syslog(logger));
+            }
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            if self.journald {
+                loggers.push(ServiceLoggerImpl::Journald(self.journald_logger()?));
+            }
+            #[cfg(any(feature = "gelf"))]
+            if let Some(gelf_transport) = self.gelf_transport.as_ref() {
+                loggers.push(ServiceLoggerImpl::Gelf(self.gelf_logger(gelf_transport)?));
+            }
+            #[cfg(any(feature = "fluentd"))]
+            if let Some(fluentd_address) = self.fluentd_address.as_deref() {
+                let logger = self.fluentd_logger(fluentd_address)?;
+                loggers.push(ServiceLoggerImpl::Fluentd(logger));
+            }
+            let logger = LoggerServiceDispatcher {
+                filters,
+                sender,
+                #[cfg(any(feature = "tcp"))]
+                tcp_dropped_events,
+            };
             let service = DefaultLoggerService { receiver, loggers };
-            thread::spawn(move || service.run());
-            Arc::new(DefaultLogger::Async(logger))
+            (Arc::new(DefaultLogger::Async(logger)), Some(service))
         } else {
+            #[cfg(any(feature = "tcp"))]
+            if self.tcp_address.is_some() {
+                return Err(LoggerBuildError::UnsupportedConfiguration(
+                    "tcp logger is not supported for syncing mode.".to_owned(),
+                ));
+            }
+            #[cfg(any(feature = "syslog"))]
+            if let Some(SyslogTransport::Tcp(..)) = self.syslog_transport {
+                return Err(LoggerBuildError::UnsupportedConfiguration(
+                    "syslog over tcp is not supported for syncing mode.".to_owned(),
+                ));
+            }
+            #[cfg(any(feature = "gelf"))]
+            if let Some(GelfTransport::Tcp(_)) = self.gelf_transport {
+                return Err(LoggerBuildError::UnsupportedConfiguration(
+                    "gelf over tcp is not supported for syncing mode.".to_owned(),
+                ));
+            }
+            #[cfg(any(feature = "fluentd"))]
+            if self.fluentd_address.is_some() {
+                return Err(LoggerBuildError::UnsupportedConfiguration(
+                    "fluentd logger is not supported for syncing mode.".to_owned(),
+                ));
+            }
             let mut loggers = vec![];
             {
                 let logger = if let Some(file_path) = &self.file {
                     SyncLoggerImpl::LocalFile(LocalFileLogger {
-                        printer: FileWriter::new(file_path.clone()),
+                        printer: self.file_writer(file_path)?,
                         filter,
                         formatter: StandardFormatter,
                     })
@@ -220,25 +1145,82 @@ impl DefaultLoggerBuilder {
                 };
                 loggers.push(logger);
             }
-            #[cfg(any(feature = "tcp"))]
-            if let Some(_) = self.tcp_address.as_deref() {
-                panic!("tcp logger is not supported for syncing mode.")
-            }
             #[cfg(any(feature = "airbrake"))]
             if let Some(airbrake_endpoint) = self.airbrake_endpoint() {
                 let environment = self.airbrake_environment.clone();
-                let logger =
-                    SyncLoggerImpl::Airbrake(AirbrakeLogger::new(airbrake_endpoint, environment));
+                let logger = SyncLoggerImpl::Airbrake(
+                    AirbrakeLogger::try_new(airbrake_endpoint, environment)
+                        .map_err(|err| LoggerBuildError::InvalidEndpoint(err.to_string()))?,
+                );
                 loggers.push(logger);
             }
-            if loggers.len() > 1 {
+            #[cfg(any(feature = "loki"))]
+            if let Some(loki_url) = self.loki_url.as_deref() {
+                loggers.push(SyncLoggerImpl::Loki(self.loki_logger(loki_url)?));
+            }
+            #[cfg(any(feature = "elasticsearch"))]
+            if let Some(elasticsearch_url) = self.elasticsearch_url.as_deref() {
+                let logger = self.elasticsearch_logger(elasticsearch_url)?;
+                loggers.push(SyncLoggerImpl::Elasticsearch(logger));
+            }
+            #[cfg(any(feature = "splunk"))]
+            if let (Some(splunk_url), Some(splunk_token)) =
+                (self.splunk_url.as_deref(), self.splunk_token.as_deref())
+            {
+                let logger = self.splunk_logger(splunk_url, splunk_token)?;
+                loggers.push(SyncLoggerImpl::Splunk(logger));
+            }
+            #[cfg(any(feature = "sentry"))]
+            if let Some(sentry_dsn) = self.sentry_dsn.as_deref() {
+                let logger = SentryLogger::try_new(
+                    sentry_dsn,
+                    self.sentry_environment.clone(),
+                    self.sentry_release.clone(),
+                )
+                .map_err(|err| LoggerBuildError::InvalidEndpoint(err.to_string()))?;
+                loggers.push(SyncLoggerImpl::Sentry(logger));
+            }
+            #[cfg(any(feature = "otlp"))]
+            if let Some(otlp_url) = self.otlp_url.as_deref() {
+                loggers.push(SyncLoggerImpl::Otlp(self.otlp_logger(otlp_url)?));
+            }
+            #[cfg(any(feature = "syslog"))]
+            if let Some(syslog_transport) = self.syslog_transport.as_ref() {
+                let logger = self.syslog_logger(syslog_transport)?;
+                loggers.push(SyncLoggerImpl::Syslog(logger));
+            }
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            if self.journald {
+                loggers.push(SyncLoggerImpl::Journald(self.journald_logger()?));
+            }
+            #[cfg(any(feature = "gelf"))]
+            if let Some(gelf_transport) = self.gelf_transport.as_ref() {
+                loggers.push(SyncLoggerImpl::Gelf(self.gelf_logger(gelf_transport)?));
+            }
+            let logger = if loggers.len() > 1 {
                 Arc::new(DefaultLogger::SyncMulti(loggers))
             } else {
                 let logger = loggers.into_iter().next().unwrap();
                 Arc::new(DefaultLogger::Sync(logger))
-            }
+            };
+            (logger, None)
         };
-        crate::set_global_logger(logger.clone());
-        logger
+        Ok(logger_and_service)
     }
 }
+
+/// File name of the running executable, empty if it cannot be told.
+#[cfg(any(
+    feature = "otlp",
+    feature = "syslog",
+    all(target_os = "linux", feature = "journald")
+))]
+fn executable_name() -> String {
+    env::current_exe()
+        .ok()
+        .and_then(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_default()
+}
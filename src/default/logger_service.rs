@@ -7,6 +7,7 @@ pub enum LoggerServiceEvent {
     Flush(SyncSender<()>),
 }
 
+#[allow(clippy::large_enum_variant)]
 pub enum ServiceLoggerImpl {
     LocalConsole(LocalConsoleLogger),
     LocalFile(LocalFileLogger),
@@ -14,6 +15,24 @@ pub enum ServiceLoggerImpl {
     Tcp(TcpLogger),
     #[cfg(any(feature = "airbrake"))]
     Airbrake(AirbrakeLogger),
+    #[cfg(any(feature = "loki"))]
+    Loki(LokiLogger),
+    #[cfg(any(feature = "elasticsearch"))]
+    Elasticsearch(ElasticsearchLogger),
+    #[cfg(any(feature = "splunk"))]
+    Splunk(SplunkLogger),
+    #[cfg(any(feature = "sentry"))]
+    Sentry(SentryLogger),
+    #[cfg(any(feature = "otlp"))]
+    Otlp(OtlpLogger),
+    #[cfg(any(feature = "syslog"))]
+    Syslog(SyslogLogger),
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    Journald(JournaldLogger),
+    #[cfg(any(feature = "gelf"))]
+    Gelf(GelfLogger),
+    #[cfg(any(feature = "fluentd"))]
+    Fluentd(FluentdLogger),
 }
 
 impl Logger for ServiceLoggerImpl {
@@ -25,6 +44,24 @@ impl Logger for ServiceLoggerImpl {
             ServiceLoggerImpl::Tcp(inner) => inner.enabled(metadata),
             #[cfg(any(feature = "airbrake"))]
             ServiceLoggerImpl::Airbrake(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "loki"))]
+            ServiceLoggerImpl::Loki(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "elasticsearch"))]
+            ServiceLoggerImpl::Elasticsearch(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "splunk"))]
+            ServiceLoggerImpl::Splunk(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "sentry"))]
+            ServiceLoggerImpl::Sentry(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "otlp"))]
+            ServiceLoggerImpl::Otlp(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "syslog"))]
+            ServiceLoggerImpl::Syslog(inner) => inner.enabled(metadata),
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            ServiceLoggerImpl::Journald(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "gelf"))]
+            ServiceLoggerImpl::Gelf(inner) => inner.enabled(metadata),
+            #[cfg(any(feature = "fluentd"))]
+            ServiceLoggerImpl::Fluentd(inner) => inner.enabled(metadata),
         }
     }
 
@@ -36,6 +73,24 @@ impl Logger for ServiceLoggerImpl {
             ServiceLoggerImpl::Tcp(inner) => Logger::record(inner, event),
             #[cfg(any(feature = "airbrake"))]
             ServiceLoggerImpl::Airbrake(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "loki"))]
+            ServiceLoggerImpl::Loki(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "elasticsearch"))]
+            ServiceLoggerImpl::Elasticsearch(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "splunk"))]
+            ServiceLoggerImpl::Splunk(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "sentry"))]
+            ServiceLoggerImpl::Sentry(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "otlp"))]
+            ServiceLoggerImpl::Otlp(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "syslog"))]
+            ServiceLoggerImpl::Syslog(inner) => Logger::record(inner, event),
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            ServiceLoggerImpl::Journald(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "gelf"))]
+            ServiceLoggerImpl::Gelf(inner) => Logger::record(inner, event),
+            #[cfg(any(feature = "fluentd"))]
+            ServiceLoggerImpl::Fluentd(inner) => Logger::record(inner, event),
         }
     }
 
@@ -47,6 +102,24 @@ impl Logger for ServiceLoggerImpl {
             ServiceLoggerImpl::Tcp(inner) => inner.flush(),
             #[cfg(any(feature = "airbrake"))]
             ServiceLoggerImpl::Airbrake(inner) => inner.flush(),
+            #[cfg(any(feature = "loki"))]
+            ServiceLoggerImpl::Loki(inner) => inner.flush(),
+            #[cfg(any(feature = "elasticsearch"))]
+            ServiceLoggerImpl::Elasticsearch(inner) => inner.flush(),
+            #[cfg(any(feature = "splunk"))]
+            ServiceLoggerImpl::Splunk(inner) => inner.flush(),
+            #[cfg(any(feature = "sentry"))]
+            ServiceLoggerImpl::Sentry(inner) => inner.flush(),
+            #[cfg(any(feature = "otlp"))]
+            ServiceLoggerImpl::Otlp(inner) => inner.flush(),
+            #[cfg(any(feature = "syslog"))]
+            ServiceLoggerImpl::Syslog(inner) => inner.flush(),
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            ServiceLoggerImpl::Journald(inner) => inner.flush(),
+            #[cfg(any(feature = "gelf"))]
+            ServiceLoggerImpl::Gelf(inner) => inner.flush(),
+            #[cfg(any(feature = "fluentd"))]
+            ServiceLoggerImpl::Fluentd(inner) => inner.flush(),
         }
     }
 }
@@ -60,6 +133,24 @@ impl AsyncLogger for ServiceLoggerImpl {
             ServiceLoggerImpl::Tcp(inner) => AsyncLogger::record(inner, event),
             #[cfg(any(feature = "airbrake"))]
             ServiceLoggerImpl::Airbrake(inner) => AsyncLogger::record(inner, event),
+            #[cfg(any(feature = "loki"))]
+            ServiceLoggerImpl::Loki(inner) => AsyncLogger::record(inner, event),
+            #[cfg(any(feature = "elasticsearch"))]
+            ServiceLoggerImpl::Elasticsearch(inner) => AsyncLogger::record(inner, event),
+            #[cfg(any(feature = "splunk"))]
+            ServiceLoggerImpl::Splunk(inner) => AsyncLogger::record(inner, event),
+            #[cfg(any(feature = "sentry"))]
+            ServiceLoggerImpl::Sentry(inner) => AsyncLogger::record(inner, event),
+            #[cfg(any(feature = "otlp"))]
+            ServiceLoggerImpl::Otlp(inner) => AsyncLogger::record(inner, event),
+            #[cfg(any(feature = "syslog"))]
+            ServiceLoggerImpl::Syslog(inner) => AsyncLogger::record(inner, event),
+            #[cfg(all(target_os = "linux", feature = "journald"))]
+            ServiceLoggerImpl::Journald(inner) => AsyncLogger::record(inner, event),
+            #[cfg(any(feature = "gelf"))]
+            ServiceLoggerImpl::Gelf(inner) => AsyncLogger::record(inner, event),
+            #[cfg(any(feature = "fluentd"))]
+            ServiceLoggerImpl::Fluentd(inner) => AsyncLogger::record(inner, event),
         }
     }
 }
@@ -76,7 +167,7 @@ impl DefaultLoggerService {
             match event {
                 LoggerServiceEvent::LogEvent(event) => {
                     for logger in &self.loggers {
-                        if logger.enabled(&event.metadata()) {
+                        if logger.enabled(event.metadata()) {
                             AsyncLogger::record(logger, &event)
                         }
                     }
@@ -27,3 +27,45 @@ pub use json_formatter::JsonFormatter;
 mod airbrake_formatter;
 #[cfg(any(feature = "airbrake"))]
 pub use airbrake_formatter::AirbrakeFormatter;
+
+#[cfg(any(feature = "loki"))]
+mod loki_formatter;
+#[cfg(any(feature = "loki"))]
+pub use loki_formatter::LokiFormatter;
+
+#[cfg(any(feature = "elasticsearch"))]
+mod elasticsearch_formatter;
+#[cfg(any(feature = "elasticsearch"))]
+pub use elasticsearch_formatter::{ElasticsearchFormatter, InvalidIndexPattern};
+
+#[cfg(any(feature = "splunk"))]
+mod splunk_formatter;
+#[cfg(any(feature = "splunk"))]
+pub use splunk_formatter::SplunkFormatter;
+
+#[cfg(any(feature = "sentry"))]
+mod sentry_formatter;
+#[cfg(any(feature = "sentry"))]
+pub use sentry_formatter::SentryFormatter;
+
+#[cfg(any(feature = "otlp"))]
+mod otlp_formatter;
+#[cfg(any(feature = "otlp"))]
+pub use otlp_formatter::OtlpFormatter;
+
+#[cfg(any(feature = "syslog"))]
+mod syslog_formatter;
+#[cfg(any(feature = "syslog"))]
+pub use syslog_formatter::{SyslogFacility, SyslogFormat, SyslogFormatter};
+#[cfg(any(feature = "journald"))]
+mod journald_formatter;
+#[cfg(any(feature = "journald"))]
+pub use journald_formatter::JournaldFormatter;
+#[cfg(any(feature = "gelf"))]
+mod gelf_formatter;
+#[cfg(any(feature = "gelf"))]
+pub use gelf_formatter::GelfFormatter;
+#[cfg(any(feature = "fluentd"))]
+mod fluentd_formatter;
+#[cfg(any(feature = "fluentd"))]
+pub use fluentd_formatter::FluentdFormatter;
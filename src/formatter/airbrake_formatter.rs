@@ -1,7 +1,6 @@
 use super::{AsyncFormatter, Formatter};
 use crate::{AsyncEvent, Event, Level};
 use serde::{Serialize, Serializer};
-use serde_json;
 use std::{
     collections::HashMap,
     env,
@@ -21,9 +20,7 @@ impl AirbrakeFormatter {
 impl Formatter for AirbrakeFormatter {
     fn format(&self, event: &Event) -> Result<String, Error> {
         let message = event.message().map(fmt::format).unwrap_or("".to_owned());
-        let backtrace = event
-            .backtrace()
-            .map(|backtrace| parse_backtrace(backtrace));
+        let backtrace = event.backtrace().map(parse_backtrace);
         let error_info = ErrorInfo {
             type_: "".to_owned(),
             message,
@@ -40,7 +37,7 @@ impl Formatter for AirbrakeFormatter {
             os: Some(env::consts::OS.to_owned()),
             hostname,
             language: None,
-            version: Some(format!("{}", env!("CARGO_PKG_VERSION"))),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
             url: None,
             user_agent: None,
             user_addr: None,
@@ -64,9 +61,7 @@ impl Formatter for AirbrakeFormatter {
 impl AsyncFormatter for AirbrakeFormatter {
     fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
         let message = event.message().unwrap_or("").to_owned();
-        let backtrace = event
-            .backtrace()
-            .map(|backtrace| parse_backtrace(backtrace));
+        let backtrace = event.backtrace().map(parse_backtrace);
         let error_info = ErrorInfo {
             type_: "".to_owned(),
             message,
@@ -83,7 +78,7 @@ impl AsyncFormatter for AirbrakeFormatter {
             os: Some(env::consts::OS.to_owned()),
             hostname,
             language: None,
-            version: Some(format!("{}", env!("CARGO_PKG_VERSION"))),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
             url: None,
             user_agent: None,
             user_addr: None,
@@ -128,8 +123,7 @@ pub struct ErrorInfo {
 
 fn parse_backtrace(s: &str) -> Vec<BacktraceInfo> {
     let backtraces: Vec<&str> = s
-        .split("\n")
-        .into_iter()
+        .split('\n')
         .filter(|s| !s.is_empty())
         .map(|s| s.trim())
         .collect();
@@ -138,10 +132,9 @@ fn parse_backtrace(s: &str) -> Vec<BacktraceInfo> {
     let mut backtrace_iter = backtraces.into_iter();
     loop {
         if let Some(t) = backtrace_iter.next() {
-            if t.starts_with("at ") {
-                let position_part = &t[3..];
-                let position_info: Vec<&str> = position_part.split(":").into_iter().collect();
-                if position_info.len() > 0 {
+            if let Some(position_part) = t.strip_prefix("at ") {
+                let position_info: Vec<&str> = position_part.split(':').collect();
+                if !position_info.is_empty() {
                     item.file = Some(position_info[0].to_owned())
                 }
                 if position_info.len() > 1 {
@@ -265,9 +258,9 @@ pub struct NotifierInfo {
 impl Default for NotifierInfo {
     fn default() -> Self {
         Self {
-            name: Some(format!("{}", env!("CARGO_PKG_NAME"))),
-            version: Some(format!("{}", env!("CARGO_PKG_VERSION"))),
-            url: Some(format!("{}", env!("CARGO_PKG_REPOSITORY"))),
+            name: Some(env!("CARGO_PKG_NAME").to_string()),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            url: Some(env!("CARGO_PKG_REPOSITORY").to_string()),
         }
     }
 }
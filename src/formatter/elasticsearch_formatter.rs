@@ -0,0 +1,113 @@
+use super::{AsyncFormatter, Formatter, JsonFormatter};
+use crate::{AsyncEvent, Event};
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, SecondsFormat, Utc,
+};
+use serde_json::{json, Map, Value};
+use std::{
+    error,
+    fmt::{self, Error, Write},
+};
+
+/// Formats an event as a `_bulk` index action followed by the document, two lines which
+/// [`BatchFormat::NdJson`](crate::BatchFormat::NdJson) joins into a bulk request body.
+///
+/// The document is what [`JsonFormatter`] makes of the event plus an RFC 3339
+/// `@timestamp`, the index name is the pattern expanded with the event timestamp
+/// as in [`chrono::format::strftime`], e.g. `logs-%Y.%m.%d`, and lower cased since
+/// Elasticsearch rejects upper case index names.
+pub struct ElasticsearchFormatter {
+    index_pattern: String,
+}
+
+impl ElasticsearchFormatter {
+    /// Fails when `index_pattern` holds an unknown strftime specifier.
+    pub fn new(index_pattern: String) -> Result<Self, InvalidIndexPattern> {
+        if StrftimeItems::new(&index_pattern).any(|item| item == Item::Error) {
+            return Err(InvalidIndexPattern(index_pattern));
+        }
+        Ok(Self { index_pattern })
+    }
+
+    fn format_action(&self, timestamp: &DateTime<Utc>, document: String) -> Result<String, Error> {
+        let mut w = String::new();
+        let mut index = String::new();
+        write!(index, "{}", timestamp.format(&self.index_pattern))?;
+        let action = json!({ "index": { "_index": index.to_lowercase() } });
+        let mut source = Map::new();
+        source.insert(
+            "@timestamp".to_owned(),
+            json!(timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        );
+        match serde_json::from_str(&document).map_err(|_| Error)? {
+            Value::Object(fields) => source.extend(fields),
+            // keep anything but an object as the message of the document.
+            value => {
+                source.insert("message".to_owned(), value);
+            }
+        }
+        write!(w, "{}\n{}", action, Value::Object(source))?;
+        Ok(w)
+    }
+}
+
+/// Index pattern rejected by [`ElasticsearchFormatter::new`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidIndexPattern(String);
+
+impl fmt::Display for InvalidIndexPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid elasticsearch index pattern: {}", self.0)
+    }
+}
+
+impl error::Error for InvalidIndexPattern {}
+
+impl Formatter for ElasticsearchFormatter {
+    fn format(&self, event: &Event) -> Result<String, Error> {
+        let document = Formatter::format(&JsonFormatter, event)?;
+        self.format_action(event.timestamp(), document)
+    }
+}
+
+impl AsyncFormatter for ElasticsearchFormatter {
+    fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
+        let document = AsyncFormatter::format(&JsonFormatter, event)?;
+        self.format_action(event.timestamp(), document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(document: &str) -> String {
+        let timestamp = "2026-10-18T13:25:10.123Z".parse().unwrap();
+        ElasticsearchFormatter::new("Logs-%Y.%m.%d".to_owned())
+            .unwrap()
+            .format_action(&timestamp, document.to_owned())
+            .unwrap()
+    }
+
+    #[test]
+    fn timestamp_is_added_to_the_document() {
+        assert_eq!(
+            format(r#"{"level":"INFO","message":"hi"}"#),
+            "{\"index\":{\"_index\":\"logs-2026.10.18\"}}\n\
+             {\"@timestamp\":\"2026-10-18T13:25:10.123Z\",\"level\":\"INFO\",\"message\":\"hi\"}"
+        );
+    }
+
+    #[test]
+    fn empty_and_non_object_documents() {
+        assert!(format("{}").ends_with("\n{\"@timestamp\":\"2026-10-18T13:25:10.123Z\"}"));
+        assert!(format("[1]")
+            .ends_with("\n{\"@timestamp\":\"2026-10-18T13:25:10.123Z\",\"message\":[1]}"));
+    }
+
+    #[test]
+    fn unknown_specifiers_are_rejected() {
+        assert!(ElasticsearchFormatter::new("logs-%Q".to_owned()).is_err());
+    }
+}
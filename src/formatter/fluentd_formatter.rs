@@ -0,0 +1,78 @@
+use super::{AsyncFormatter, Formatter, JsonFormatter};
+use crate::{AsyncEvent, Event, Metadata};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::fmt::{Error, Write};
+
+/// Placeholders of a tag template, filled in from the metadata of every event.
+const PLACEHOLDERS: [&str; 3] = ["{level}", "{target}", "{module}"];
+
+/// Formats an event as a Fluentd entry, a json object of the `tag`, the `time` as seconds
+/// and nanoseconds, and the `record`, what [`JsonFormatter`] makes of the event. It is
+/// turned into MessagePack by [`FluentdWriter`](crate::prelude::FluentdWriter).
+///
+/// The tag is made from a template in which `{level}` is replaced by the lower case
+/// level, `{target}` by the target and `{module}` by the module path, `::` becoming `.`
+/// in both, e.g. `app.{level}` tags an error as `app.error`.
+pub struct FluentdFormatter {
+    tag_template: String,
+}
+
+impl FluentdFormatter {
+    pub fn new(tag_template: String) -> Self {
+        Self { tag_template }
+    }
+
+    /// Whether every `{...}` in `tag_template` is a known placeholder.
+    pub(crate) fn is_valid_template(tag_template: &str) -> bool {
+        let mut rest = tag_template;
+        while let Some(start) = rest.find('{') {
+            rest = &rest[start..];
+            match PLACEHOLDERS.iter().find(|p| rest.starts_with(*p)) {
+                Some(placeholder) => rest = &rest[placeholder.len()..],
+                None => return false,
+            }
+        }
+        !rest.contains('}')
+    }
+
+    fn tag(&self, metadata: &Metadata) -> String {
+        let level = metadata.level().to_string().to_lowercase();
+        self.tag_template
+            .replace("{level}", &level)
+            .replace("{target}", &metadata.target().replace("::", "."))
+            .replace("{module}", &metadata.module_path().replace("::", "."))
+    }
+
+    fn format_entry(
+        &self,
+        metadata: &Metadata,
+        timestamp: &DateTime<Utc>,
+        record: String,
+    ) -> Result<String, Error> {
+        let mut w = String::new();
+        write!(
+            w,
+            "{{\"tag\":{},\"time\":[{},{}],\"record\":{}}}",
+            json!(self.tag(metadata)),
+            timestamp.timestamp(),
+            timestamp.timestamp_subsec_nanos(),
+            record
+        )?;
+        Ok(w)
+    }
+}
+
+impl Formatter for FluentdFormatter {
+    fn format(&self, event: &Event) -> Result<String, Error> {
+        let record = Formatter::format(&JsonFormatter, event)?;
+        self.format_entry(event.metadata(), event.timestamp(), record)
+    }
+}
+
+impl AsyncFormatter for FluentdFormatter {
+    fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
+        let record = AsyncFormatter::format(&JsonFormatter, event)?;
+        self.format_entry(event.metadata(), event.timestamp(), record)
+    }
+}
@@ -0,0 +1,124 @@
+use super::{AsyncFormatter, Formatter};
+use crate::{AsyncEvent, Event, Key, Level, Metadata};
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Number, Value};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Error, Write},
+};
+
+/// Formats events as GELF 1.1 messages for Graylog, without any transport framing.
+///
+/// The backtrace of an event becomes its `full_message`, the code location and every
+/// key-value are sent as additional fields, e.g. `user_id` as `_user_id`.
+pub struct GelfFormatter;
+
+impl GelfFormatter {
+    fn format_message(
+        &self,
+        metadata: &Metadata,
+        timestamp: &DateTime<Utc>,
+        message: String,
+        keys_and_values: &BTreeMap<Key, Value>,
+        backtrace: Option<&str>,
+    ) -> Result<String, Error> {
+        let mut w = String::new();
+        let mut fields = Map::new();
+        fields.insert("version".to_owned(), Value::from("1.1"));
+        fields.insert(
+            "host".to_owned(),
+            Value::from(
+                metadata
+                    .hostname()
+                    .filter(|hostname| !hostname.is_empty())
+                    .unwrap_or("unknown"),
+            ),
+        );
+        fields.insert("short_message".to_owned(), Value::from(message));
+        if let Some(backtrace) = backtrace {
+            fields.insert("full_message".to_owned(), Value::from(backtrace));
+        }
+        let seconds = timestamp.timestamp() as f64
+            + f64::from(timestamp.timestamp_subsec_micros()) / 1_000_000.0;
+        if let Some(seconds) = Number::from_f64(seconds) {
+            fields.insert("timestamp".to_owned(), Value::Number(seconds));
+        }
+        fields.insert("level".to_owned(), Value::from(severity(metadata.level())));
+        for (key, value) in keys_and_values {
+            if let Some(name) = additional_field_name(key.deref()) {
+                let value = match value {
+                    Value::String(_) | Value::Number(_) => value.clone(),
+                    value => Value::String(value.to_string()),
+                };
+                fields.insert(name, value);
+            }
+        }
+        // the code location goes last so that key-values cannot replace it.
+        fields.insert("_file".to_owned(), Value::from(metadata.file()));
+        fields.insert("_line".to_owned(), Value::from(metadata.line()));
+        fields.insert("_module".to_owned(), Value::from(metadata.module_path()));
+        fields.insert("_target".to_owned(), Value::from(metadata.target()));
+        if let Some(thread_name) = metadata.thread_name() {
+            fields.insert("_thread_name".to_owned(), Value::from(thread_name));
+        }
+        write!(w, "{}", Value::Object(fields))?;
+        Ok(w)
+    }
+}
+
+impl Formatter for GelfFormatter {
+    fn format(&self, event: &Event) -> Result<String, Error> {
+        let message = event.message().map(fmt::format).unwrap_or_default();
+        self.format_message(
+            event.metadata(),
+            event.timestamp(),
+            message,
+            &event.get_json_keys_and_values(),
+            event.backtrace(),
+        )
+    }
+}
+
+impl AsyncFormatter for GelfFormatter {
+    fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
+        let message = event.message().unwrap_or("").to_owned();
+        self.format_message(
+            event.metadata(),
+            event.timestamp(),
+            message,
+            event.keys_and_values(),
+            event.backtrace(),
+        )
+    }
+}
+
+/// Syslog severity of `level`, which GELF expects as `level`.
+fn severity(level: Level) -> u8 {
+    match level {
+        Level::Crash => 2,
+        Level::Error => 3,
+        Level::Warn => 4,
+        Level::Info => 6,
+        Level::Debug | Level::Trace => 7,
+    }
+}
+
+/// Additional field names are `_` followed by word characters, dots and dashes. `_id` is
+/// reserved by Graylog and left out.
+fn additional_field_name(key: &str) -> Option<String> {
+    let name: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.is_empty() || name == "id" {
+        None
+    } else {
+        Some(format!("_{}", name))
+    }
+}
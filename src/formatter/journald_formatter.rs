@@ -0,0 +1,119 @@
+use super::{AsyncFormatter, Formatter};
+use crate::{AsyncEvent, Event, Key, Level, Metadata};
+use serde_json::{Map, Value};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Error, Write},
+};
+
+/// Formats an event as the fields of a journal entry, a json object of field names to
+/// values turned into the native protocol by [`JournaldWriter`](crate::prelude::JournaldWriter).
+///
+/// Besides `MESSAGE` and `PRIORITY` the entry holds the code location as `CODE_FILE`,
+/// `CODE_LINE` and `CODE_MODULE`, the thread as `TID`, and every key-value as a field
+/// named after the key in upper case, e.g. `user_id` becomes `USER_ID`.
+pub struct JournaldFormatter {
+    syslog_identifier: String,
+}
+
+impl JournaldFormatter {
+    pub fn new(syslog_identifier: String) -> Self {
+        Self { syslog_identifier }
+    }
+
+    fn format_entry(
+        &self,
+        metadata: &Metadata,
+        message: String,
+        keys_and_values: &BTreeMap<Key, Value>,
+        backtrace: Option<&str>,
+    ) -> Result<String, Error> {
+        let mut w = String::new();
+        let mut fields = Map::new();
+        // key-values go first so that they cannot replace the well-known fields.
+        for (key, value) in keys_and_values {
+            if let Some(name) = field_name(key.deref()) {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                fields.insert(name, Value::String(value));
+            }
+        }
+        let mut insert = |name: &str, value: String| {
+            fields.insert(name.to_owned(), Value::String(value));
+        };
+        insert("MESSAGE", message);
+        insert("PRIORITY", priority(metadata.level()).to_string());
+        insert("SYSLOG_IDENTIFIER", self.syslog_identifier.clone());
+        insert("CODE_FILE", metadata.file().to_owned());
+        insert("CODE_LINE", metadata.line().to_string());
+        insert("CODE_MODULE", metadata.module_path().to_owned());
+        insert("TID", metadata.thread_id().to_string());
+        insert("TARGET", metadata.target().to_owned());
+        if let Some(thread_name) = metadata.thread_name() {
+            insert("THREAD_NAME", thread_name.to_owned());
+        }
+        if let Some(backtrace) = backtrace {
+            insert("BACKTRACE", backtrace.to_owned());
+        }
+        write!(w, "{}", Value::Object(fields))?;
+        Ok(w)
+    }
+}
+
+impl Formatter for JournaldFormatter {
+    fn format(&self, event: &Event) -> Result<String, Error> {
+        let message = event.message().map(fmt::format).unwrap_or_default();
+        self.format_entry(
+            event.metadata(),
+            message,
+            &event.get_json_keys_and_values(),
+            event.backtrace(),
+        )
+    }
+}
+
+impl AsyncFormatter for JournaldFormatter {
+    fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
+        let message = event.message().unwrap_or("").to_owned();
+        self.format_entry(
+            event.metadata(),
+            message,
+            event.keys_and_values(),
+            event.backtrace(),
+        )
+    }
+}
+
+/// Syslog severity of `level`, which journald expects as `PRIORITY`.
+fn priority(level: Level) -> u8 {
+    match level {
+        Level::Crash => 2,
+        Level::Error => 3,
+        Level::Warn => 4,
+        Level::Info => 6,
+        Level::Debug | Level::Trace => 7,
+    }
+}
+
+/// Journal field names are made of upper case letters, digits and underscores, do not
+/// start with a digit or an underscore, which is reserved for trusted fields, and are
+/// at most 64 characters long.
+fn field_name(key: &str) -> Option<String> {
+    let name: String = key
+        .chars()
+        .map(|c| match c {
+            'a'..='z' => c.to_ascii_uppercase(),
+            'A'..='Z' | '0'..='9' => c,
+            _ => '_',
+        })
+        .skip_while(|c| *c == '_' || c.is_ascii_digit())
+        .take(64)
+        .collect();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
@@ -0,0 +1,64 @@
+use super::{AsyncFormatter, Formatter, JsonFormatter};
+use crate::{AsyncEvent, Event, Metadata};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    fmt::{Error, Write},
+};
+
+/// Formats an event as a Loki stream holding just this event, to be merged with the
+/// other streams of a batch by [`LokiEncoder`](crate::writer::LokiEncoder).
+///
+/// The stream is labelled with the static labels plus `level`, `target` and `hostname`,
+/// the line is what [`JsonFormatter`] makes of the event.
+pub struct LokiFormatter {
+    labels: BTreeMap<String, String>,
+}
+
+impl LokiFormatter {
+    pub fn new(labels: BTreeMap<String, String>) -> Self {
+        Self { labels }
+    }
+
+    fn format_stream(
+        &self,
+        metadata: &Metadata,
+        timestamp: &DateTime<Utc>,
+        line: String,
+    ) -> Result<String, Error> {
+        let mut w = String::new();
+        let mut labels = self.labels.clone();
+        labels.insert(
+            "level".to_owned(),
+            metadata.level().to_string().to_lowercase(),
+        );
+        labels.insert("target".to_owned(), metadata.target().to_owned());
+        if let Some(hostname) = metadata.hostname() {
+            labels.insert("hostname".to_owned(), hostname.to_owned());
+        }
+        // nanoseconds since the epoch as a string, as the push api expects.
+        let nanos = i128::from(timestamp.timestamp()) * 1_000_000_000
+            + i128::from(timestamp.timestamp_subsec_nanos());
+        let stream = json!({
+            "stream": labels,
+            "values": [[nanos.to_string(), line]],
+        });
+        write!(w, "{}", stream)?;
+        Ok(w)
+    }
+}
+
+impl Formatter for LokiFormatter {
+    fn format(&self, event: &Event) -> Result<String, Error> {
+        let line = Formatter::format(&JsonFormatter, event)?;
+        self.format_stream(event.metadata(), event.timestamp(), line)
+    }
+}
+
+impl AsyncFormatter for LokiFormatter {
+    fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
+        let line = AsyncFormatter::format(&JsonFormatter, event)?;
+        self.format_stream(event.metadata(), event.timestamp(), line)
+    }
+}
@@ -0,0 +1,137 @@
+use super::{AsyncFormatter, Formatter};
+use crate::{AsyncEvent, Event, Key, Level, Metadata};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Error, Write},
+};
+
+/// Formats an event as an OTLP `LogRecord` in the JSON encoding, along with the
+/// attributes of the resource which emitted it, to be grouped into an export request by
+/// [`OtlpProtocol`](crate::OtlpProtocol).
+///
+/// The resource is described by `service.name` and `host.name`, the record by the
+/// message as body and the key-values plus the code location and thread as attributes.
+/// @see https://opentelemetry.io/docs/specs/otel/logs/data-model/
+pub struct OtlpFormatter {
+    service_name: String,
+}
+
+impl OtlpFormatter {
+    pub fn new(service_name: String) -> Self {
+        Self { service_name }
+    }
+
+    fn format_record(
+        &self,
+        metadata: &Metadata,
+        timestamp: &DateTime<Utc>,
+        message: String,
+        keys_and_values: &BTreeMap<Key, Value>,
+        backtrace: Option<&str>,
+    ) -> Result<String, Error> {
+        let mut w = String::new();
+        let mut resource = vec![key_value("service.name", json!(self.service_name))];
+        if let Some(hostname) = metadata.hostname() {
+            resource.push(key_value("host.name", json!(hostname)));
+        }
+        let mut attributes: Vec<Value> = keys_and_values
+            .iter()
+            .map(|(key, value)| key_value(key.deref(), value.clone()))
+            .collect();
+        attributes.push(key_value("code.filepath", json!(metadata.file())));
+        attributes.push(key_value("code.lineno", json!(metadata.line())));
+        attributes.push(key_value("code.namespace", json!(metadata.module_path())));
+        attributes.push(key_value("thread.id", json!(metadata.thread_id())));
+        if let Some(thread_name) = metadata.thread_name() {
+            attributes.push(key_value("thread.name", json!(thread_name)));
+        }
+        if let Some(backtrace) = backtrace {
+            attributes.push(key_value("exception.stacktrace", json!(backtrace)));
+        }
+        let (severity_number, severity_text) = severity(metadata.level());
+        // nanoseconds since the epoch, 64 bit integers are strings in the json encoding.
+        let nanos = i128::from(timestamp.timestamp()) * 1_000_000_000
+            + i128::from(timestamp.timestamp_subsec_nanos());
+        let record = json!({
+            "resource": { "attributes": resource },
+            "logRecord": {
+                "timeUnixNano": nanos.to_string(),
+                "observedTimeUnixNano": nanos.to_string(),
+                "severityNumber": severity_number,
+                "severityText": severity_text,
+                "body": any_value(json!(message)),
+                "attributes": attributes,
+            },
+        });
+        write!(w, "{}", record)?;
+        Ok(w)
+    }
+}
+
+impl Formatter for OtlpFormatter {
+    fn format(&self, event: &Event) -> Result<String, Error> {
+        let message = event.message().map(fmt::format).unwrap_or_default();
+        self.format_record(
+            event.metadata(),
+            event.timestamp(),
+            message,
+            &event.get_json_keys_and_values(),
+            event.backtrace(),
+        )
+    }
+}
+
+impl AsyncFormatter for OtlpFormatter {
+    fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
+        let message = event.message().unwrap_or("").to_owned();
+        self.format_record(
+            event.metadata(),
+            event.timestamp(),
+            message,
+            event.keys_and_values(),
+            event.backtrace(),
+        )
+    }
+}
+
+/// `SeverityNumber` and `SeverityText` of the OTLP log data model.
+fn severity(level: Level) -> (u8, &'static str) {
+    match level {
+        Level::Trace => (1, "TRACE"),
+        Level::Debug => (5, "DEBUG"),
+        Level::Info => (9, "INFO"),
+        Level::Warn => (13, "WARN"),
+        Level::Error => (17, "ERROR"),
+        Level::Crash => (21, "FATAL"),
+    }
+}
+
+fn key_value(key: &str, value: Value) -> Value {
+    json!({ "key": key, "value": any_value(value) })
+}
+
+/// The `AnyValue` holding `value`.
+fn any_value(value: Value) -> Value {
+    match value {
+        Value::Null => json!({}),
+        Value::Bool(value) => json!({ "boolValue": value }),
+        Value::Number(number) => match number.as_i64() {
+            Some(value) => json!({ "intValue": value.to_string() }),
+            None => json!({ "doubleValue": number.as_f64() }),
+        },
+        Value::String(value) => json!({ "stringValue": value }),
+        Value::Array(values) => {
+            let values: Vec<Value> = values.into_iter().map(any_value).collect();
+            json!({ "arrayValue": { "values": values } })
+        }
+        Value::Object(fields) => {
+            let values: Vec<Value> = fields
+                .into_iter()
+                .map(|(key, value)| key_value(&key, value))
+                .collect();
+            json!({ "kvlistValue": { "values": values } })
+        }
+    }
+}
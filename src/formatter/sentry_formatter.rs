@@ -0,0 +1,197 @@
+use super::{AsyncFormatter, Formatter};
+use crate::{AsyncEvent, Event, Key, Level, Metadata};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::{
+    collections::{hash_map::RandomState, BTreeMap},
+    fmt::{self, Error, Write},
+    hash::{BuildHasher, Hasher},
+};
+
+mod backtrace;
+
+/// Formats an event as a Sentry envelope holding a single event item.
+///
+/// Key-values are sent as `extra`, the hostname as `server_name`, and the backtrace of
+/// a crash as the stack trace of a `panic` exception.
+/// @see https://develop.sentry.dev/sdk/envelopes/
+pub struct SentryFormatter {
+    environment: Option<String>,
+    release: Option<String>,
+}
+
+impl SentryFormatter {
+    pub fn new(environment: Option<String>, release: Option<String>) -> Self {
+        Self {
+            environment,
+            release,
+        }
+    }
+
+    fn format_envelope(
+        &self,
+        metadata: &Metadata,
+        timestamp: &DateTime<Utc>,
+        message: String,
+        extra: &BTreeMap<Key, serde_json::Value>,
+        backtrace: Option<&str>,
+    ) -> Result<String, Error> {
+        let event_id = event_id();
+        let exception = backtrace.map(|backtrace| {
+            // sentry lists frames from the outermost call to the innermost one.
+            let mut frames: Vec<StackFrame> = backtrace::parse_backtrace(backtrace)
+                .into_iter()
+                .map(StackFrame::from)
+                .collect();
+            frames.reverse();
+            ExceptionValues {
+                values: vec![Exception {
+                    type_: "panic",
+                    value: message.clone(),
+                    stacktrace: StackTrace { frames },
+                }],
+            }
+        });
+        let event = SentryEvent {
+            event_id: &event_id,
+            timestamp: timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+            platform: "native",
+            level: level(metadata.level()),
+            logger: metadata.target(),
+            message: &message,
+            server_name: metadata.hostname(),
+            environment: self.environment.as_deref(),
+            release: self.release.as_deref(),
+            extra,
+            exception,
+            sdk: Sdk {
+                name: env!("CARGO_PKG_NAME"),
+                version: env!("CARGO_PKG_VERSION"),
+            },
+        };
+        let payload = serde_json::to_string(&event).map_err(|_| Error)?;
+
+        let mut w = String::new();
+        let header = json!({
+            "event_id": event_id,
+            "sent_at": Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true),
+        });
+        let item_header = json!({ "type": "event", "length": payload.len() });
+        write!(w, "{}\n{}\n{}\n", header, item_header, payload)?;
+        Ok(w)
+    }
+}
+
+impl Formatter for SentryFormatter {
+    fn format(&self, event: &Event) -> Result<String, Error> {
+        let message = event.message().map(fmt::format).unwrap_or_default();
+        self.format_envelope(
+            event.metadata(),
+            event.timestamp(),
+            message,
+            &event.get_json_keys_and_values(),
+            event.backtrace(),
+        )
+    }
+}
+
+impl AsyncFormatter for SentryFormatter {
+    fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
+        let message = event.message().unwrap_or("").to_owned();
+        self.format_envelope(
+            event.metadata(),
+            event.timestamp(),
+            message,
+            event.keys_and_values(),
+            event.backtrace(),
+        )
+    }
+}
+
+/// @see https://develop.sentry.dev/sdk/event-payloads/
+#[derive(Serialize)]
+struct SentryEvent<'a> {
+    event_id: &'a str,
+    timestamp: String,
+    platform: &'static str,
+    level: &'static str,
+    logger: &'a str,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    server_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    release: Option<&'a str>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    extra: &'a BTreeMap<Key, serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exception: Option<ExceptionValues>,
+    sdk: Sdk,
+}
+
+#[derive(Serialize)]
+struct ExceptionValues {
+    values: Vec<Exception>,
+}
+
+#[derive(Serialize)]
+struct Exception {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    value: String,
+    stacktrace: StackTrace,
+}
+
+#[derive(Serialize)]
+struct StackTrace {
+    frames: Vec<StackFrame>,
+}
+
+#[derive(Serialize)]
+struct StackFrame {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    function: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lineno: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    colno: Option<usize>,
+}
+
+impl From<backtrace::Frame> for StackFrame {
+    fn from(frame: backtrace::Frame) -> Self {
+        Self {
+            function: frame.function,
+            filename: frame.file,
+            lineno: frame.line,
+            colno: frame.column,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Sdk {
+    name: &'static str,
+    version: &'static str,
+}
+
+fn level(level: Level) -> &'static str {
+    match level {
+        Level::Crash => "fatal",
+        Level::Error => "error",
+        Level::Warn => "warning",
+        Level::Info => "info",
+        Level::Debug | Level::Trace => "debug",
+    }
+}
+
+/// 32 random hex digits, good enough to tell events apart but not for anything else.
+fn event_id() -> String {
+    // every RandomState is seeded differently, per process and per call.
+    let high = RandomState::new().build_hasher().finish();
+    let low = RandomState::new().build_hasher().finish();
+    format!("{:016x}{:016x}", high, low)
+}
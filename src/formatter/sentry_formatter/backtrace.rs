@@ -0,0 +1,78 @@
+/// One frame of a backtrace as rendered by the `Debug` implementation of `backtrace::Backtrace`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct Frame {
+    pub function: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/// Parse a backtrace made of `N: function` lines, each followed by an optional
+/// `at file:line:column` line, into its frames, innermost first.
+pub(super) fn parse_backtrace(s: &str) -> Vec<Frame> {
+    let mut frames = vec![];
+    let mut frame = Frame::default();
+    for line in s.split('\n').map(str::trim).filter(|line| !line.is_empty()) {
+        if let Some(position) = line.strip_prefix("at ") {
+            // split from the end, the file may contain colons itself, e.g. `C:\`.
+            let mut parts = position.rsplitn(3, ':');
+            let column = parts.next();
+            let line = parts.next();
+            match (parts.next(), line, column) {
+                (Some(file), Some(line), Some(column)) => {
+                    frame.file = Some(file.to_owned());
+                    frame.line = line.parse().ok();
+                    frame.column = column.parse().ok();
+                }
+                (None, Some(file), Some(line)) => {
+                    frame.file = Some(file.to_owned());
+                    frame.line = line.parse().ok();
+                }
+                _ => frame.file = Some(position.to_owned()),
+            }
+            frames.push(frame);
+            frame = Frame::default();
+        } else {
+            if frame != Frame::default() {
+                frames.push(frame);
+            }
+            // drop the frame number in front of the function name.
+            let function = match line.split_once(": ") {
+                Some((index, function)) if index.chars().all(|c| c.is_ascii_digit()) => function,
+                _ => line,
+            };
+            frame = Frame {
+                function: Some(function.to_owned()),
+                ..Frame::default()
+            };
+        }
+    }
+    if frame != Frame::default() {
+        frames.push(frame);
+    }
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_with_and_without_positions() {
+        let backtrace = "   0: app::main\n             at src/main.rs:10:5\n   1: std::rt::lang_start\n   2: app::run\n             at C:\\app\\src\\lib.rs:7:3\n";
+        let frame = |function: &str, file: Option<&str>, line, column| Frame {
+            function: Some(function.to_owned()),
+            file: file.map(str::to_owned),
+            line,
+            column,
+        };
+        assert_eq!(
+            parse_backtrace(backtrace),
+            [
+                frame("app::main", Some("src/main.rs"), Some(10), Some(5)),
+                frame("std::rt::lang_start", None, None, None),
+                frame("app::run", Some("C:\\app\\src\\lib.rs"), Some(7), Some(3)),
+            ]
+        );
+    }
+}
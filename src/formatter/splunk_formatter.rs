@@ -0,0 +1,71 @@
+use super::{AsyncFormatter, Formatter, JsonFormatter};
+use crate::{AsyncEvent, Event, Metadata};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::fmt::{Error, Write};
+
+/// Formats an event as a Splunk HTTP Event Collector envelope.
+///
+/// `time` is the event timestamp in seconds with microsecond precision, `host` the
+/// hostname of the event, and `event` what [`JsonFormatter`] makes of it. `source`,
+/// `sourcetype` and `index` are left to the collector's defaults unless set.
+#[derive(Default)]
+pub struct SplunkFormatter {
+    source: Option<String>,
+    sourcetype: Option<String>,
+    index: Option<String>,
+}
+
+impl SplunkFormatter {
+    pub fn new(source: Option<String>, sourcetype: Option<String>, index: Option<String>) -> Self {
+        Self {
+            source,
+            sourcetype,
+            index,
+        }
+    }
+
+    fn format_envelope(
+        &self,
+        metadata: &Metadata,
+        timestamp: &DateTime<Utc>,
+        event: String,
+    ) -> Result<String, Error> {
+        let mut w = String::new();
+        write!(
+            w,
+            "{{\"time\":{}.{:06},",
+            timestamp.timestamp(),
+            timestamp.timestamp_subsec_micros()
+        )?;
+        if let Some(hostname) = metadata.hostname() {
+            write!(w, "\"host\":{},", json!(hostname))?;
+        }
+        for (name, value) in [
+            ("source", &self.source),
+            ("sourcetype", &self.sourcetype),
+            ("index", &self.index),
+        ] {
+            if let Some(value) = value {
+                write!(w, "\"{}\":{},", name, json!(value))?;
+            }
+        }
+        // the event is already json, embed it as is rather than parsing it again.
+        write!(w, "\"event\":{}}}", event)?;
+        Ok(w)
+    }
+}
+
+impl Formatter for SplunkFormatter {
+    fn format(&self, event: &Event) -> Result<String, Error> {
+        let line = Formatter::format(&JsonFormatter, event)?;
+        self.format_envelope(event.metadata(), event.timestamp(), line)
+    }
+}
+
+impl AsyncFormatter for SplunkFormatter {
+    fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
+        let line = AsyncFormatter::format(&JsonFormatter, event)?;
+        self.format_envelope(event.metadata(), event.timestamp(), line)
+    }
+}
@@ -0,0 +1,215 @@
+use super::{AsyncFormatter, Formatter};
+use crate::{AsyncEvent, Event, Key, Level, Metadata};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Error, Write},
+    process,
+};
+
+/// SD-ID of the STRUCTURED-DATA element holding the key-values of an event. 32473 is the
+/// private enterprise number reserved for documentation, collectors accept it as any other.
+const STRUCTURED_DATA_ID: &str = "data@32473";
+
+/// Header layout of syslog messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogFormat {
+    /// `<PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID MSGID STRUCTURED-DATA MSG`, with the
+    /// key-values as STRUCTURED-DATA.
+    Rfc5424,
+    /// `<PRI>Mmm dd hh:mm:ss HOSTNAME TAG[PID]: MSG` in local time, with the key-values
+    /// appended to the message as `key=value`.
+    Rfc3164,
+}
+
+/// Syslog facility, the kind of program a message comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogFacility {
+    Kern = 0,
+    User,
+    Mail,
+    Daemon,
+    Auth,
+    Syslog,
+    Lpr,
+    News,
+    Uucp,
+    Cron,
+    Authpriv,
+    Ftp,
+    Local0 = 16,
+    Local1,
+    Local2,
+    Local3,
+    Local4,
+    Local5,
+    Local6,
+    Local7,
+}
+
+/// Formats events as syslog messages, without any transport framing.
+pub struct SyslogFormatter {
+    format: SyslogFormat,
+    facility: SyslogFacility,
+    app_name: String,
+    include_hostname: bool,
+}
+
+impl SyslogFormatter {
+    pub fn new(format: SyslogFormat, facility: SyslogFacility, app_name: String) -> Self {
+        Self {
+            format,
+            facility,
+            app_name,
+            include_hostname: true,
+        }
+    }
+
+    /// Leave the hostname out of RFC 3164 headers, as `syslog(3)` does for the local
+    /// socket where the daemon fills it in itself.
+    pub fn include_hostname(mut self, include_hostname: bool) -> Self {
+        self.include_hostname = include_hostname;
+        self
+    }
+
+    fn format_message(
+        &self,
+        metadata: &Metadata,
+        timestamp: &DateTime<Utc>,
+        message: &str,
+        keys_and_values: &BTreeMap<Key, Value>,
+    ) -> Result<String, Error> {
+        let mut w = String::new();
+        let priority = self.facility as u8 * 8 + severity(metadata.level());
+        let hostname = metadata.hostname().filter(|hostname| !hostname.is_empty());
+        match self.format {
+            SyslogFormat::Rfc5424 => {
+                write!(
+                    w,
+                    "<{}>1 {} {} {} {} - ",
+                    priority,
+                    timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+                    header_field(hostname.unwrap_or("-"), 255),
+                    header_field(&self.app_name, 48),
+                    process::id(),
+                )?;
+                if keys_and_values.is_empty() {
+                    w.push('-');
+                } else {
+                    write!(w, "[{}", STRUCTURED_DATA_ID)?;
+                    for (key, value) in keys_and_values {
+                        write!(w, " {}=\"", param_name(key.deref()))?;
+                        escape_param_value(&mut w, &plain_value(value));
+                        w.push('"');
+                    }
+                    w.push(']');
+                }
+                if !message.is_empty() {
+                    write!(w, " {}", message)?;
+                }
+            }
+            SyslogFormat::Rfc3164 => {
+                write!(
+                    w,
+                    "<{}>{}",
+                    priority,
+                    timestamp.with_timezone(&Local).format("%b %e %H:%M:%S")
+                )?;
+                if let Some(hostname) = hostname.filter(|_| self.include_hostname) {
+                    write!(w, " {}", header_field(hostname, 255))?;
+                }
+                write!(
+                    w,
+                    " {}[{}]: {}",
+                    header_field(&self.app_name, 32),
+                    process::id(),
+                    message
+                )?;
+                for (key, value) in keys_and_values {
+                    write!(w, " {}={}", key.deref(), plain_value(value))?;
+                }
+            }
+        }
+        Ok(w)
+    }
+}
+
+impl Formatter for SyslogFormatter {
+    fn format(&self, event: &Event) -> Result<String, Error> {
+        let message = event.message().map(fmt::format).unwrap_or_default();
+        self.format_message(
+            event.metadata(),
+            event.timestamp(),
+            &message,
+            &event.get_json_keys_and_values(),
+        )
+    }
+}
+
+impl AsyncFormatter for SyslogFormatter {
+    fn format(&self, event: &AsyncEvent) -> Result<String, Error> {
+        self.format_message(
+            event.metadata(),
+            event.timestamp(),
+            event.message().unwrap_or(""),
+            event.keys_and_values(),
+        )
+    }
+}
+
+/// Syslog severity of `level`, a crash is critical rather than an emergency of the system.
+fn severity(level: Level) -> u8 {
+    match level {
+        Level::Crash => 2,
+        Level::Error => 3,
+        Level::Warn => 4,
+        Level::Info => 6,
+        Level::Debug | Level::Trace => 7,
+    }
+}
+
+/// Header fields are printable ASCII without spaces, `-` when empty.
+fn header_field(value: &str, max_len: usize) -> String {
+    let field: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect();
+    if field.is_empty() {
+        "-".to_owned()
+    } else {
+        field
+    }
+}
+
+/// PARAM-NAME of RFC 5424, printable ASCII without `=`, ` `, `]` and `"`, at most 32 long.
+fn param_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+        .take(32)
+        .collect();
+    if name.is_empty() {
+        "_".to_owned()
+    } else {
+        name
+    }
+}
+
+fn escape_param_value(w: &mut String, value: &str) {
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            w.push('\\');
+        }
+        w.push(c);
+    }
+}
+
+/// Strings as they are, anything else as json.
+fn plain_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
@@ -21,6 +21,7 @@ impl Key {
         self.borrow()
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn deref(&self) -> &str {
         self.0.deref()
     }
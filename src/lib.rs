@@ -1,6 +1,21 @@
 pub mod prelude {
-    #[cfg(any(feature = "airbrake"))]
+    #[cfg(any(feature = "fluentd"))]
+    pub use crate::writer::FluentdWriter;
+    #[cfg(any(feature = "gelf"))]
+    pub use crate::writer::GelfWriter;
+    #[cfg(any(
+        feature = "airbrake",
+        feature = "loki",
+        feature = "elasticsearch",
+        feature = "splunk",
+        feature = "sentry",
+        feature = "otlp"
+    ))]
     pub use crate::writer::HttpWriter;
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    pub use crate::writer::JournaldWriter;
+    #[cfg(any(feature = "syslog"))]
+    pub use crate::writer::SyslogWriter;
     #[cfg(any(feature = "tcp"))]
     pub use crate::writer::TcpWriter;
     pub use crate::{
@@ -25,11 +40,62 @@ pub use event::{AsyncEvent, Event};
 pub use filter::{Filter, StandardFilter, StandardFilterBuilder};
 #[cfg(any(feature = "airbrake"))]
 pub use formatter::AirbrakeFormatter;
+#[cfg(any(feature = "fluentd"))]
+pub use formatter::FluentdFormatter;
+#[cfg(any(feature = "gelf"))]
+pub use formatter::GelfFormatter;
+#[cfg(any(feature = "journald"))]
+pub use formatter::JournaldFormatter;
+#[cfg(any(feature = "loki"))]
+pub use formatter::LokiFormatter;
+#[cfg(any(feature = "otlp"))]
+pub use formatter::OtlpFormatter;
+#[cfg(any(feature = "sentry"))]
+pub use formatter::SentryFormatter;
+#[cfg(any(feature = "splunk"))]
+pub use formatter::SplunkFormatter;
 pub use formatter::{AsyncFormatter, Formatter, JsonFormatter, StandardFormatter};
+#[cfg(any(feature = "elasticsearch"))]
+pub use formatter::{ElasticsearchFormatter, InvalidIndexPattern};
+#[cfg(any(feature = "syslog"))]
+pub use formatter::{SyslogFacility, SyslogFormat, SyslogFormatter};
 pub use kv::{Key, KeyValue, Schema, Value, Visitor};
 pub use logger::{AsyncLogger, Logger};
 pub use metadata::{Level, Metadata};
-pub use writer::Writer;
+#[cfg(any(feature = "elasticsearch"))]
+pub use writer::ElasticsearchEncoder;
+#[cfg(any(feature = "fluentd"))]
+pub use writer::FluentdWriterBuilder;
+#[cfg(any(feature = "loki"))]
+pub use writer::LokiEncoder;
+#[cfg(any(feature = "otlp"))]
+pub use writer::OtlpProtocol;
+#[cfg(any(feature = "splunk"))]
+pub use writer::SplunkEncoder;
+#[cfg(any(feature = "syslog"))]
+pub use writer::SyslogTransport;
+#[cfg(any(feature = "tls"))]
+pub use writer::TcpTlsConfig;
+pub use writer::{
+    ArchiveCompression, ArchivePolicy, FileDurability, FileRotation, FileWriterBuilder,
+    RotationPeriod, RotationTimezone, Writer,
+};
+#[cfg(any(feature = "tcp"))]
+pub use writer::{BacklogPolicy, ReconnectPolicy, TcpFraming, TcpWriterBuilder};
+#[cfg(any(
+    feature = "airbrake",
+    feature = "loki",
+    feature = "elasticsearch",
+    feature = "splunk",
+    feature = "sentry",
+    feature = "otlp"
+))]
+pub use writer::{
+    BatchEncoder, BatchFormat, BatchPolicy, DeliveryOutcome, DeliveryReport, HttpCompression,
+    HttpWriterBuilder, RetryPolicy,
+};
+#[cfg(any(feature = "gelf"))]
+pub use writer::{GelfCompression, GelfTransport};
 
 use once_cell::sync::OnceCell;
 use std::sync::Arc;
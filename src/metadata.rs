@@ -8,17 +8,16 @@ thread_local!(
 );
 
 fn thread_id() -> u64 {
-    THREAD_ID.with(|id| id.clone())
+    THREAD_ID.with(|id| *id)
 }
 
-#[allow(dead_code)]
 static HOSTNAME: Lazy<Option<String>> = Lazy::new(|| {
     hostname::get()
         .ok()
         .and_then(|name| name.into_string().ok())
 });
 
-fn hostname() -> Option<&'static str> {
+pub(crate) fn hostname() -> Option<&'static str> {
     HOSTNAME.as_deref()
 }
 
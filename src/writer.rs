@@ -6,14 +6,64 @@ mod stderr_writer;
 pub use stderr_writer::StderrWriter;
 
 mod file_writer;
-pub use file_writer::FileWriter;
+pub use file_writer::{
+    ArchiveCompression, ArchivePolicy, FileDurability, FileRotation, FileWriter, FileWriterBuilder,
+    RotationPeriod, RotationTimezone,
+};
 
 #[cfg(any(feature = "tcp"))]
 mod tcp_writer;
+#[cfg(any(feature = "tls"))]
+pub use tcp_writer::TcpTlsConfig;
 #[cfg(any(feature = "tcp"))]
-pub use tcp_writer::TcpWriter;
+pub use tcp_writer::{BacklogPolicy, ReconnectPolicy, TcpFraming, TcpWriter, TcpWriterBuilder};
 
-#[cfg(any(feature = "airbrake"))]
+#[cfg(any(feature = "syslog"))]
+mod syslog_writer;
+#[cfg(any(feature = "syslog"))]
+pub use syslog_writer::{SyslogTransport, SyslogWriter};
+
+#[cfg(all(target_os = "linux", feature = "journald"))]
+mod journald_writer;
+#[cfg(all(target_os = "linux", feature = "journald"))]
+pub use journald_writer::JournaldWriter;
+
+#[cfg(any(feature = "gelf"))]
+mod gelf_writer;
+#[cfg(any(feature = "gelf"))]
+pub use gelf_writer::{GelfCompression, GelfTransport, GelfWriter};
+
+#[cfg(any(feature = "fluentd"))]
+mod fluentd_writer;
+#[cfg(any(feature = "fluentd"))]
+pub use fluentd_writer::{FluentdWriter, FluentdWriterBuilder};
+
+#[cfg(any(
+    feature = "airbrake",
+    feature = "loki",
+    feature = "elasticsearch",
+    feature = "splunk",
+    feature = "sentry",
+    feature = "otlp"
+))]
 mod http_writer;
-#[cfg(any(feature = "airbrake"))]
-pub use http_writer::HttpWriter;
+#[cfg(any(feature = "elasticsearch"))]
+pub use http_writer::ElasticsearchEncoder;
+#[cfg(any(feature = "loki"))]
+pub use http_writer::LokiEncoder;
+#[cfg(any(feature = "otlp"))]
+pub use http_writer::OtlpProtocol;
+#[cfg(any(feature = "splunk"))]
+pub use http_writer::SplunkEncoder;
+#[cfg(any(
+    feature = "airbrake",
+    feature = "loki",
+    feature = "elasticsearch",
+    feature = "splunk",
+    feature = "sentry",
+    feature = "otlp"
+))]
+pub use http_writer::{
+    BatchEncoder, BatchFormat, BatchPolicy, DeliveryOutcome, DeliveryReport, HttpCompression,
+    HttpWriter, HttpWriterBuilder, RetryPolicy,
+};
@@ -1,31 +1,466 @@
 use super::Writer;
-use std::{io::Write, sync::RwLock};
+use chrono::{DateTime, Utc};
+use std::{
+    fs, io,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock, Weak,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Buffer size used when buffering has not been enabled, lines are flushed right away then.
+const LINE_BUFFER_CAPACITY: usize = 8 * 1024;
+/// Shortest period of the thread flushing buffered lines, shorter ones keep it spinning.
+const MIN_FLUSH_INTERVAL: Duration = Duration::from_millis(10);
+
+mod archive;
+pub use archive::{ArchiveCompression, ArchivePolicy};
+use archive::{ArchiveJob, Archiver};
+
+mod durability;
+pub use durability::FileDurability;
+use durability::GroupCommit;
+
+mod rotation;
+pub use rotation::{FileRotation, RotationPeriod, RotationTimezone};
+
+#[cfg(all(unix, feature = "sighup"))]
+mod sighup;
 
 pub struct FileWriter {
-    log_file: RwLock<std::fs::File>,
+    pattern: PathBuf,
+    rotation: FileRotation,
+    log_file: Arc<RwLock<LogFile>>,
+    buffer: Option<FileBuffer>,
+    sync_on_flush: bool,
+    durability: FileDurability,
+    group_commit: GroupCommit,
+    archiver: Option<Archiver>,
+    /// Set from outside the write path (e.g. by the SIGHUP handler) to reopen on the next write.
+    reopen_requested: Arc<AtomicBool>,
+    check_path_interval: Option<Duration>,
+    #[cfg(all(unix, feature = "sighup"))]
+    _sighup: Option<sighup::SighupRegistration>,
 }
 
-impl FileWriter {
-    pub fn new(log_file: std::path::PathBuf) -> Self {
-        let file = std::fs::OpenOptions::new()
+#[derive(Debug, Clone, Copy)]
+struct FileBuffer {
+    capacity: usize,
+    max_flush_interval: Duration,
+}
+
+struct LogFile {
+    file: io::BufWriter<fs::File>,
+    path: PathBuf,
+    size: u64,
+    next_rollover: Option<DateTime<Utc>>,
+    identity: Option<FileIdentity>,
+    checked_at: Instant,
+    flushed_at: Instant,
+}
+
+impl LogFile {
+    fn open(
+        path: PathBuf,
+        next_rollover: Option<DateTime<Utc>>,
+        buffer_capacity: usize,
+    ) -> io::Result<Self> {
+        let file = fs::OpenOptions::new()
             .append(true)
             .create(true)
-            .open(log_file)
-            .expect("Unable to open log file");
+            .open(&path)?;
+        let metadata = file.metadata()?;
+        Ok(Self {
+            file: io::BufWriter::with_capacity(buffer_capacity, file),
+            path,
+            size: metadata.len(),
+            next_rollover,
+            identity: FileIdentity::of(&metadata),
+            checked_at: Instant::now(),
+            flushed_at: Instant::now(),
+        })
+    }
+
+    fn flush(&mut self, sync: bool) -> io::Result<()> {
+        self.file.flush()?;
+        self.flushed_at = Instant::now();
+        if sync {
+            self.file.get_ref().sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Whether the path no longer refers to the file we are appending to,
+    /// e.g. because an external logrotate moved it away.
+    fn is_replaced(&self) -> bool {
+        match fs::metadata(&self.path) {
+            Ok(metadata) => FileIdentity::of(&metadata) != self.identity,
+            Err(_) => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileIdentity {
+    dev: u64,
+    ino: u64,
+}
+
+impl FileIdentity {
+    #[cfg(unix)]
+    fn of(metadata: &fs::Metadata) -> Option<Self> {
+        use std::os::unix::fs::MetadataExt;
+        Some(Self {
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn of(_metadata: &fs::Metadata) -> Option<Self> {
+        None
+    }
+}
+
+pub struct FileWriterBuilder {
+    log_file: PathBuf,
+    rotation: FileRotation,
+    archive: ArchivePolicy,
+    #[cfg(all(unix, feature = "sighup"))]
+    reopen_on_sighup: bool,
+    check_path_interval: Option<Duration>,
+    buffer: Option<FileBuffer>,
+    sync_on_flush: bool,
+    durability: FileDurability,
+}
+
+impl FileWriterBuilder {
+    /// `log_file` may contain `{hostname}` and `{pid}` placeholders, and a `{date}`
+    /// placeholder when rotating by time.
+    pub fn new<T: Into<PathBuf>>(log_file: T) -> Self {
         Self {
-            log_file: RwLock::new(file),
+            log_file: log_file.into(),
+            rotation: FileRotation::Never,
+            archive: ArchivePolicy::default(),
+            #[cfg(all(unix, feature = "sighup"))]
+            reopen_on_sighup: false,
+            check_path_interval: None,
+            buffer: None,
+            sync_on_flush: false,
+            durability: FileDurability::None,
         }
     }
+
+    pub fn rotation(&mut self, rotation: FileRotation) -> &mut Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Compress and prune the files rotated out according to `archive`.
+    pub fn archive(&mut self, archive: ArchivePolicy) -> &mut Self {
+        self.archive = archive;
+        self
+    }
+
+    /// Reopen the log file when the process receives SIGHUP, as sent by logrotate's `postrotate`.
+    #[cfg(all(unix, feature = "sighup"))]
+    pub fn reopen_on_sighup(&mut self, reopen_on_sighup: bool) -> &mut Self {
+        self.reopen_on_sighup = reopen_on_sighup;
+        self
+    }
+
+    /// At most once per `interval`, check on write whether the log file has been moved
+    /// or replaced and reopen it if so.
+    pub fn check_path_interval(&mut self, interval: Duration) -> &mut Self {
+        self.check_path_interval = Some(interval);
+        self
+    }
+
+    /// Buffer up to `capacity` bytes in memory instead of writing every line through.
+    /// Buffered lines reach the file on [`FileWriter::flush`], when the buffer is full,
+    /// and at the latest `max_flush_interval` after the previous flush, which is at least
+    /// 10 milliseconds.
+    pub fn buffer(&mut self, capacity: usize, max_flush_interval: Duration) -> &mut Self {
+        self.buffer = Some(FileBuffer {
+            capacity,
+            max_flush_interval: max_flush_interval.max(MIN_FLUSH_INTERVAL),
+        });
+        self
+    }
+
+    /// `fsync` the log file every time [`FileWriter::flush`] is called.
+    pub fn sync_on_flush(&mut self, sync_on_flush: bool) -> &mut Self {
+        self.sync_on_flush = sync_on_flush;
+        self
+    }
+
+    /// Make writes return only once the line is on stable storage, see [`FileDurability`].
+    pub fn durability(&mut self, durability: FileDurability) -> &mut Self {
+        self.durability = durability;
+        self
+    }
+
+    pub fn build(&mut self) -> FileWriter {
+        self.try_build().expect("Unable to open log file")
+    }
+
+    pub fn try_build(&mut self) -> io::Result<FileWriter> {
+        let buffer_capacity = self
+            .buffer
+            .map(|buffer| buffer.capacity)
+            .unwrap_or(LINE_BUFFER_CAPACITY);
+        let file = open_log_file(&self.log_file, self.rotation, buffer_capacity)?;
+        let archiver = if self.archive.is_empty() {
+            None
+        } else {
+            let max_files = match self.rotation {
+                FileRotation::Size { max_files, .. } if max_files > 0 => Some(max_files),
+                _ => None,
+            };
+            rotation::archive_name_parts(&self.log_file, self.rotation).map(
+                |(dir, prefix, suffix)| {
+                    Archiver::spawn(self.archive.clone(), dir, prefix, suffix, max_files)
+                },
+            )
+        };
+        if let Some(archiver) = &archiver {
+            // apply the retention policy to archives left over by earlier runs.
+            archiver.submit(ArchiveJob {
+                active: file.path.clone(),
+            });
+        }
+        let reopen_requested = Arc::new(AtomicBool::new(false));
+        #[cfg(all(unix, feature = "sighup"))]
+        let sighup = if self.reopen_on_sighup {
+            sighup::SighupRegistration::register(reopen_requested.clone())
+                .map_err(|err| eprintln!("Unable to register SIGHUP handler: {}", err))
+                .ok()
+        } else {
+            None
+        };
+        let log_file = Arc::new(RwLock::new(file));
+        if let Some(buffer) = self.buffer {
+            let log_file = Arc::downgrade(&log_file);
+            thread::spawn(move || flush_periodically(log_file, buffer.max_flush_interval));
+        }
+        Ok(FileWriter {
+            pattern: self.log_file.clone(),
+            rotation: self.rotation,
+            log_file,
+            buffer: self.buffer,
+            sync_on_flush: self.sync_on_flush,
+            durability: self.durability,
+            group_commit: GroupCommit::default(),
+            archiver,
+            reopen_requested,
+            check_path_interval: self.check_path_interval,
+            #[cfg(all(unix, feature = "sighup"))]
+            _sighup: sighup,
+        })
+    }
 }
 
-impl Writer for FileWriter {
-    fn write(&self, log: String) {
-        let mut file = self
+impl FileWriter {
+    pub fn builder<T: Into<PathBuf>>(log_file: T) -> FileWriterBuilder {
+        FileWriterBuilder::new(log_file)
+    }
+
+    pub fn new(log_file: PathBuf) -> Self {
+        FileWriterBuilder::new(log_file).build()
+    }
+
+    pub fn try_new(log_file: PathBuf) -> io::Result<Self> {
+        FileWriterBuilder::new(log_file).try_build()
+    }
+
+    pub fn with_rotation(log_file: PathBuf, rotation: FileRotation) -> Self {
+        FileWriterBuilder::new(log_file).rotation(rotation).build()
+    }
+
+    /// Write buffered lines to the log file, and `fsync` it if enabled.
+    pub fn flush(&self) {
+        let mut log_file = self
+            .log_file
+            .write()
+            .expect("log file lock must be handled...");
+        if let Err(err) = log_file.flush(self.sync_on_flush) {
+            eprintln!("Unable to flush log file: {}", err);
+        }
+    }
+
+    /// Write `log` as a line like [`Writer::write`], but report the line as failed rather
+    /// than printing the error. With a durable mode an error means that the line may not
+    /// have reached stable storage.
+    pub fn try_write(&self, log: String) -> io::Result<()> {
+        let mut log_file = self
             .log_file
             .write()
             .expect("log file lock must be handled...");
-        if let Err(err) = writeln!(file, "{}", log) {
-            eprintln!("Unable to write to log file: {}", err.to_string());
+        if self.should_reopen(&mut log_file) {
+            if let Err(err) = self.reopen_locked(&mut log_file) {
+                eprintln!("Unable to reopen log file: {}", err);
+            }
+        }
+        let incoming = log.len() as u64 + 1;
+        if self.should_rotate(&log_file, incoming) {
+            if let Err(err) = self.rotate(&mut log_file) {
+                eprintln!("Unable to rotate log file: {}", err);
+            }
+        }
+        let mut line = log.into_bytes();
+        line.push(b'\n');
+        log_file.file.write_all(&line)?;
+        log_file.size += incoming;
+        match self.durability {
+            FileDurability::None if self.should_flush(&log_file) => log_file.flush(false),
+            FileDurability::None => Ok(()),
+            FileDurability::EveryEvent => log_file.flush(true),
+            FileDurability::GroupCommit(window) => {
+                let sequence = self.group_commit.written();
+                drop(log_file);
+                self.group_commit.wait(sequence, window, || {
+                    let mut log_file = self
+                        .log_file
+                        .write()
+                        .expect("log file lock must be handled...");
+                    let covered = self.group_commit.last_written();
+                    let file = log_file
+                        .flush(false)
+                        .and_then(|_| log_file.file.get_ref().try_clone());
+                    drop(log_file);
+                    (covered, file.and_then(|file| file.sync_data()))
+                })
+            }
+        }
+    }
+
+    /// Close the log file and open it again by path, so that writes go to a freshly
+    /// created file after it has been moved away by an external tool.
+    pub fn reopen(&self) -> io::Result<()> {
+        let mut log_file = self
+            .log_file
+            .write()
+            .expect("log file lock must be handled...");
+        self.reopen_locked(&mut log_file)
+    }
+
+    fn reopen_locked(&self, log_file: &mut LogFile) -> io::Result<()> {
+        log_file.flush(self.sync_on_close())?;
+        *log_file = open_log_file(&self.pattern, self.rotation, self.buffer_capacity())?;
+        Ok(())
+    }
+
+    fn buffer_capacity(&self) -> usize {
+        self.buffer
+            .map(|buffer| buffer.capacity)
+            .unwrap_or(LINE_BUFFER_CAPACITY)
+    }
+
+    /// Whether the file has to be synced before it is closed for a reopen or rotation.
+    fn sync_on_close(&self) -> bool {
+        self.sync_on_flush || self.durability.is_durable()
+    }
+
+    fn should_flush(&self, log_file: &LogFile) -> bool {
+        match self.buffer {
+            Some(buffer) => log_file.flushed_at.elapsed() >= buffer.max_flush_interval,
+            None => true,
+        }
+    }
+
+    fn should_reopen(&self, log_file: &mut LogFile) -> bool {
+        if self.reopen_requested.swap(false, Ordering::SeqCst) {
+            return true;
+        }
+        match self.check_path_interval {
+            Some(interval) if log_file.checked_at.elapsed() >= interval => {
+                log_file.checked_at = Instant::now();
+                log_file.is_replaced()
+            }
+            _ => false,
+        }
+    }
+
+    fn should_rotate(&self, log_file: &LogFile, incoming: u64) -> bool {
+        match self.rotation {
+            FileRotation::Never => false,
+            FileRotation::Size { max_bytes, .. } => {
+                // never rotate an empty file, otherwise a single oversized line
+                // would produce an empty archive on every write.
+                log_file.size > 0 && log_file.size + incoming > max_bytes
+            }
+            FileRotation::Time { .. } => log_file
+                .next_rollover
+                .map(|next_rollover| Utc::now() >= next_rollover)
+                .unwrap_or(false),
+        }
+    }
+
+    fn rotate(&self, log_file: &mut LogFile) -> io::Result<()> {
+        log_file.flush(self.sync_on_close())?;
+        if let FileRotation::Size { max_files, .. } = self.rotation {
+            if self.archiver.is_some() && max_files > 0 {
+                // the archiver numbers the file, it owns the archives and may be busy.
+                fs::rename(&log_file.path, rotation::staging_path(&log_file.path))?;
+            } else {
+                rotation::rotate_numbered(&log_file.path, max_files)?;
+            }
+        }
+        *log_file = open_log_file(&self.pattern, self.rotation, self.buffer_capacity())?;
+        if let Some(archiver) = &self.archiver {
+            archiver.submit(ArchiveJob {
+                active: log_file.path.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn open_log_file(
+    pattern: &Path,
+    rotation: FileRotation,
+    buffer_capacity: usize,
+) -> io::Result<LogFile> {
+    let now = Utc::now();
+    LogFile::open(
+        rotation.render_path(pattern, now),
+        rotation.next_rollover(now),
+        buffer_capacity,
+    )
+}
+
+/// Flush lines which have been sitting in the buffer for longer than `interval`
+/// while nothing else is logged. Stops once the writer has been dropped.
+fn flush_periodically(log_file: Weak<RwLock<LogFile>>, interval: Duration) {
+    loop {
+        thread::sleep(interval);
+        let log_file = match log_file.upgrade() {
+            Some(log_file) => log_file,
+            None => break,
+        };
+        let mut log_file = log_file.write().expect("log file lock must be handled...");
+        if log_file.flushed_at.elapsed() >= interval && !log_file.file.buffer().is_empty() {
+            if let Err(err) = log_file.flush(false) {
+                eprintln!("Unable to flush log file: {}", err);
+            }
+        }
+    }
+}
+
+impl Drop for FileWriter {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+impl Writer for FileWriter {
+    fn write(&self, log: String) {
+        if let Err(err) = self.try_write(log) {
+            eprintln!("Unable to write to log file: {}", err);
         }
     }
 }
@@ -0,0 +1,372 @@
+use super::rotation;
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{mpsc, Mutex},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// Suffixes a rotated archive may carry, the plain one first.
+pub(crate) const ARCHIVE_EXTENSIONS: &[&str] = &["", ".gz", ".zst"];
+/// Suffix of archives being compressed, renamed to the final name once complete.
+const TEMPORARY_EXTENSION: &str = ".tmp";
+
+/// What happens to log files once they have been rotated out.
+///
+/// Compression and pruning run on a helper thread owned by the [`FileWriter`](super::FileWriter),
+/// the active log file is never touched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchivePolicy {
+    pub compression: Option<ArchiveCompression>,
+    /// Remove archives whose last modification is older than this.
+    pub max_age: Option<Duration>,
+    /// Remove the oldest archives until the active file and the archives fit in this many bytes.
+    pub max_total_bytes: Option<u64>,
+}
+
+impl ArchivePolicy {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.compression.is_none() && self.max_age.is_none() && self.max_total_bytes.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveCompression {
+    #[cfg(any(feature = "gzip"))]
+    Gzip,
+    #[cfg(any(feature = "zstd"))]
+    Zstd,
+}
+
+impl ArchiveCompression {
+    fn extension(&self) -> &'static str {
+        match *self {
+            #[cfg(any(feature = "gzip"))]
+            ArchiveCompression::Gzip => ".gz",
+            #[cfg(any(feature = "zstd"))]
+            ArchiveCompression::Zstd => ".zst",
+        }
+    }
+
+    /// Compress `path` into `output`.
+    #[allow(unused_variables)]
+    fn compress(&self, path: &Path, output: &Path) -> io::Result<fs::File> {
+        match *self {
+            #[cfg(any(feature = "gzip"))]
+            ArchiveCompression::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(
+                    fs::File::create(output)?,
+                    flate2::Compression::default(),
+                );
+                io::copy(&mut fs::File::open(path)?, &mut encoder)?;
+                encoder.finish()
+            }
+            #[cfg(any(feature = "zstd"))]
+            ArchiveCompression::Zstd => {
+                let mut encoder = zstd::Encoder::new(fs::File::create(output)?, 0)?;
+                io::copy(&mut fs::File::open(path)?, &mut encoder)?;
+                encoder.finish()
+            }
+        }
+    }
+
+    fn target(&self, path: &Path) -> PathBuf {
+        let mut target = path.as_os_str().to_owned();
+        target.push(self.extension());
+        PathBuf::from(target)
+    }
+}
+
+pub(crate) struct ArchiveJob {
+    /// The file the writer is appending to now.
+    pub(crate) active: PathBuf,
+}
+
+/// Handle to the helper thread compressing and pruning archives.
+///
+/// The helper thread is the only one renaming or removing archives. With size based
+/// rotation the writer merely moves the full file to a staging path, see
+/// [`rotation::staging_path`], and the helper shifts it in as `path.1`, so nothing on
+/// the logging path ever waits for a compression to finish.
+pub(crate) struct Archiver {
+    sender: Mutex<mpsc::Sender<ArchiveJob>>,
+}
+
+impl Archiver {
+    /// `dir`, `prefix` and `suffix` describe which file names in `dir` are archives,
+    /// `max_files` is set for size based rotation.
+    pub(crate) fn spawn(
+        policy: ArchivePolicy,
+        dir: PathBuf,
+        prefix: String,
+        suffix: String,
+        max_files: Option<usize>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let worker = ArchiveWorker {
+            policy,
+            dir,
+            prefix,
+            suffix,
+            max_files,
+        };
+        thread::spawn(move || worker.run(receiver));
+        Self {
+            sender: Mutex::new(sender),
+        }
+    }
+
+    pub(crate) fn submit(&self, job: ArchiveJob) {
+        let sender = self
+            .sender
+            .lock()
+            .expect("archive sender lock must be handled...");
+        if sender.send(job).is_err() {
+            eprintln!("Unable to archive log file: archive thread has stopped");
+        }
+    }
+}
+
+struct ArchiveWorker {
+    policy: ArchivePolicy,
+    dir: PathBuf,
+    prefix: String,
+    suffix: String,
+    max_files: Option<usize>,
+}
+
+impl ArchiveWorker {
+    fn run(self, receiver: mpsc::Receiver<ArchiveJob>) {
+        // the thread stops once the owning writer has been dropped.
+        for job in receiver {
+            if let Some(max_files) = self.max_files {
+                if let Err(err) = self.number_staged(&job.active, max_files) {
+                    eprintln!("Unable to rotate log files: {}", err);
+                }
+            }
+            if let Some(compression) = self.policy.compression {
+                if let Err(err) = self.compress(compression, &job.active) {
+                    eprintln!("Unable to compress log files: {}", err);
+                }
+            }
+            if let Err(err) = self.prune(&job.active) {
+                eprintln!("Unable to prune log files: {}", err);
+            }
+        }
+    }
+
+    /// Shift the files staged by the writer in as `path.1`, oldest first. Files staged by
+    /// an earlier run which stopped before numbering them are picked up as well.
+    fn number_staged(&self, active: &Path, max_files: usize) -> io::Result<()> {
+        let prefix = rotation::staging_prefix(active);
+        let prefix = Path::new(&prefix).file_name().and_then(OsStr::to_str);
+        let prefix = match prefix {
+            Some(prefix) => prefix,
+            None => return Ok(()),
+        };
+        let mut staged = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let is_staged = entry
+                .file_name()
+                .to_str()
+                .map(|file_name| file_name.starts_with(prefix))
+                .unwrap_or(false);
+            if is_staged {
+                staged.push(entry.path());
+            }
+        }
+        staged.sort();
+        for path in staged {
+            rotation::shift_numbered(active, max_files)?;
+            fs::rename(&path, rotation::archive_path(active, 1))?;
+        }
+        Ok(())
+    }
+
+    /// Archives in the directory except the active file as `(path, modified, len)`.
+    fn archives(&self, active: &Path) -> io::Result<Vec<(PathBuf, SystemTime, u64)>> {
+        let mut archives = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let is_archive = file_name
+                .to_str()
+                .map(|file_name| self.is_archive(file_name))
+                .unwrap_or(false);
+            if !is_archive || Some(file_name.as_os_str()) == active.file_name() {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                archives.push((entry.path(), modified, metadata.len()));
+            }
+        }
+        Ok(archives)
+    }
+
+    /// Compress every archive which has not been compressed yet. Sweeping the directory
+    /// instead of tracking single files keeps this correct while numbered archives shift.
+    fn compress(&self, compression: ArchiveCompression, active: &Path) -> io::Result<()> {
+        for (path, _, _) in self.archives(active)? {
+            let is_compressed = ARCHIVE_EXTENSIONS[1..].iter().any(|extension| {
+                path.to_str()
+                    .map(|path| path.ends_with(extension))
+                    .unwrap_or(false)
+            });
+            if is_compressed {
+                continue;
+            }
+            // compress under a name which is not taken for an archive, so that a
+            // compression cut short never leaves a truncated archive behind.
+            let target = compression.target(&path);
+            let mut temporary = target.clone().into_os_string();
+            temporary.push(TEMPORARY_EXTENSION);
+            let temporary = PathBuf::from(temporary);
+            let output = compression.compress(&path, &temporary)?;
+            output.sync_all()?;
+            fs::rename(&temporary, &target)?;
+            fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+
+    fn is_archive(&self, file_name: &str) -> bool {
+        if file_name.ends_with(TEMPORARY_EXTENSION) {
+            return false;
+        }
+        ARCHIVE_EXTENSIONS.iter().any(|extension| {
+            file_name
+                .strip_suffix(extension)
+                .and_then(|name| name.strip_prefix(self.prefix.as_str()))
+                .map(|name| name.len() > self.suffix.len() && name.ends_with(self.suffix.as_str()))
+                .unwrap_or(false)
+        })
+    }
+
+    fn prune(&self, active: &Path) -> io::Result<()> {
+        if self.policy.max_age.is_none() && self.policy.max_total_bytes.is_none() {
+            return Ok(());
+        }
+        let mut archives = self.archives(active)?;
+        // newest first
+        archives.sort_by_key(|(_, modified, _)| std::cmp::Reverse(*modified));
+
+        let now = SystemTime::now();
+        let mut total_bytes = fs::metadata(active).map(|m| m.len()).unwrap_or(0);
+        for (path, modified, len) in archives {
+            let expired = self
+                .policy
+                .max_age
+                .map(|max_age| now.duration_since(modified).unwrap_or_default() > max_age)
+                .unwrap_or(false);
+            total_bytes += len;
+            let oversized = self
+                .policy
+                .max_total_bytes
+                .map(|max_total_bytes| total_bytes > max_total_bytes)
+                .unwrap_or(false);
+            if expired || oversized {
+                fs::remove_file(&path)?;
+                total_bytes -= len;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("logger-archive-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn worker(dir: &Path, policy: ArchivePolicy) -> ArchiveWorker {
+        ArchiveWorker {
+            policy,
+            dir: dir.to_path_buf(),
+            prefix: "app.log.".to_owned(),
+            suffix: "".to_owned(),
+            max_files: None,
+        }
+    }
+
+    /// Write the files in order, each modified a little later than the one before.
+    fn write_files(dir: &Path, files: &[(&str, usize)]) {
+        for (file_name, len) in files {
+            fs::write(dir.join(file_name), vec![b'x'; *len]).unwrap();
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut file_names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        file_names.sort();
+        file_names
+    }
+
+    #[test]
+    fn archives_older_than_max_age_are_pruned() {
+        let dir = temp_dir("age");
+        write_files(
+            &dir,
+            &[("app.log", 10), ("app.log.2.gz", 10), ("other.log", 10)],
+        );
+        thread::sleep(Duration::from_millis(1100));
+        write_files(&dir, &[("app.log.1", 10)]);
+
+        let policy = ArchivePolicy {
+            max_age: Some(Duration::from_secs(1)),
+            ..ArchivePolicy::default()
+        };
+        worker(&dir, policy).prune(&dir.join("app.log")).unwrap();
+        // the active file is just as old, but never pruned.
+        assert_eq!(file_names(&dir), ["app.log", "app.log.1", "other.log"]);
+    }
+
+    #[test]
+    fn oldest_archives_are_pruned_beyond_max_total_bytes() {
+        let dir = temp_dir("total");
+        write_files(
+            &dir,
+            &[
+                ("app.log.3", 10),
+                ("app.log.2.gz", 10),
+                ("app.log.1", 10),
+                ("app.log", 10),
+            ],
+        );
+
+        let policy = ArchivePolicy {
+            max_total_bytes: Some(25),
+            ..ArchivePolicy::default()
+        };
+        worker(&dir, policy).prune(&dir.join("app.log")).unwrap();
+        assert_eq!(file_names(&dir), ["app.log", "app.log.1"]);
+    }
+
+    #[test]
+    fn active_file_is_kept_even_when_too_large() {
+        let dir = temp_dir("active");
+        write_files(&dir, &[("app.log.1", 10), ("app.log", 100)]);
+
+        let policy = ArchivePolicy {
+            max_total_bytes: Some(50),
+            ..ArchivePolicy::default()
+        };
+        worker(&dir, policy).prune(&dir.join("app.log")).unwrap();
+        assert_eq!(file_names(&dir), ["app.log"]);
+    }
+}
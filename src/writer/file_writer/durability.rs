@@ -0,0 +1,191 @@
+use std::{
+    io,
+    sync::{Condvar, Mutex},
+    thread,
+    time::Duration,
+};
+
+/// When a written line is guaranteed to be on stable storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileDurability {
+    /// Leave it to the operating system (or to [`FileWriter::flush`](super::FileWriter::flush)).
+    None,
+    /// `sync_data` after every line before the write returns.
+    EveryEvent,
+    /// Block each write until a commit covering its line has been `sync_data`ed. A commit
+    /// happens at most once per window and is shared by every writer that waited for it.
+    GroupCommit(Duration),
+}
+
+impl Default for FileDurability {
+    fn default() -> Self {
+        FileDurability::None
+    }
+}
+
+impl FileDurability {
+    pub(crate) fn is_durable(&self) -> bool {
+        *self != FileDurability::None
+    }
+}
+
+#[derive(Default)]
+struct CommitState {
+    /// Sequence number of the last line written to the file.
+    written: u64,
+    /// Sequence number up to which lines are known to be on stable storage.
+    synced: u64,
+    /// Whether a leader is currently waiting for the window to close or syncing.
+    syncing: bool,
+    /// The last commit which failed, its lines are not known to be on stable storage.
+    failed: Option<FailedCommit>,
+}
+
+struct FailedCommit {
+    /// Lines after `from` up to and including `through` were part of the commit.
+    from: u64,
+    through: u64,
+    kind: io::ErrorKind,
+    message: String,
+}
+
+/// Coordinates the writers waiting for a group commit.
+#[derive(Default)]
+pub(crate) struct GroupCommit {
+    state: Mutex<CommitState>,
+    committed: Condvar,
+}
+
+impl GroupCommit {
+    /// Register a line that has just been written, must be called while the
+    /// file is still locked so sequence numbers follow the file order.
+    pub(crate) fn written(&self) -> u64 {
+        let mut state = self.lock();
+        state.written += 1;
+        state.written
+    }
+
+    pub(crate) fn last_written(&self) -> u64 {
+        self.lock().written
+    }
+
+    /// Block until line `sequence` has been synced. The first caller to find no commit
+    /// in progress leads the next one: it waits for `window` so that concurrent writers
+    /// can join, then runs `commit` on behalf of all of them.
+    ///
+    /// `commit` has to flush the buffer while holding the file lock, sync the file and
+    /// return [`last_written`](Self::last_written) as seen under that lock. When the sync
+    /// fails every line of the commit fails with it, they are not retried since a second
+    /// sync may succeed without the lost pages ever reaching the disk.
+    pub(crate) fn wait<F>(&self, sequence: u64, window: Duration, commit: F) -> io::Result<()>
+    where
+        F: FnOnce() -> (u64, io::Result<()>),
+    {
+        let from = {
+            let mut state = self.lock();
+            loop {
+                if let Some(failed) = &state.failed {
+                    if failed.from < sequence && sequence <= failed.through {
+                        return Err(io::Error::new(failed.kind, failed.message.clone()));
+                    }
+                }
+                if state.synced >= sequence {
+                    return Ok(());
+                }
+                if !state.syncing {
+                    state.syncing = true;
+                    break state.synced;
+                }
+                state = self
+                    .committed
+                    .wait(state)
+                    .expect("group commit lock must be handled...");
+            }
+        };
+
+        thread::sleep(window);
+        let (covered, result) = commit();
+
+        let mut state = self.lock();
+        match &result {
+            Ok(()) => state.synced = state.synced.max(covered),
+            Err(err) => {
+                state.failed = Some(FailedCommit {
+                    from,
+                    through: covered,
+                    kind: err.kind(),
+                    message: err.to_string(),
+                })
+            }
+        }
+        state.syncing = false;
+        self.committed.notify_all();
+        result
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CommitState> {
+        self.state
+            .lock()
+            .expect("group commit lock must be handled...")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{mpsc, Arc};
+
+    fn sync_failure() -> io::Error {
+        io::Error::new(io::ErrorKind::Other, "injected sync failure")
+    }
+
+    #[test]
+    fn failed_sync_fails_every_line_of_the_commit() {
+        let commit = Arc::new(GroupCommit::default());
+        let first = commit.written();
+        let second = commit.written();
+        let (started, leading) = mpsc::channel();
+        let follower = {
+            let commit = Arc::clone(&commit);
+            thread::spawn(move || {
+                // joins the commit led below once it is in progress.
+                leading.recv().unwrap();
+                commit.wait(second, Duration::ZERO, || {
+                    panic!("the follower must not sync")
+                })
+            })
+        };
+        let result = commit.wait(first, Duration::from_millis(100), || {
+            started.send(()).unwrap();
+            thread::sleep(Duration::from_millis(100));
+            (commit.last_written(), Err(sync_failure()))
+        });
+
+        assert_eq!(result.unwrap_err().to_string(), "injected sync failure");
+        let err = follower.join().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(commit.lock().synced, 0);
+    }
+
+    #[test]
+    fn lines_after_a_failed_commit_are_synced_again() {
+        let commit = GroupCommit::default();
+        let first = commit.written();
+        assert!(commit
+            .wait(first, Duration::ZERO, || (first, Err(sync_failure())))
+            .is_err());
+        // a late waiter of the failed commit still sees it fail.
+        assert!(commit
+            .wait(first, Duration::ZERO, || panic!("already failed"))
+            .is_err());
+
+        let second = commit.written();
+        commit
+            .wait(second, Duration::ZERO, || (second, Ok(())))
+            .unwrap();
+        assert_eq!(commit.lock().synced, second);
+        assert!(commit
+            .wait(first, Duration::ZERO, || panic!("already failed"))
+            .is_err());
+    }
+}
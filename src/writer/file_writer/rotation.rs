@@ -0,0 +1,420 @@
+use super::archive::ARCHIVE_EXTENSIONS;
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone, Timelike, Utc};
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    time::SystemTime,
+};
+
+const DATE_PLACEHOLDER: &str = "{date}";
+const HOSTNAME_PLACEHOLDER: &str = "{hostname}";
+const PID_PLACEHOLDER: &str = "{pid}";
+
+/// Policy deciding when the active log file is rolled over to an archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileRotation {
+    /// Append to the same file forever.
+    Never,
+    /// Roll `app.log` over to `app.log.1` once it would grow beyond `max_bytes`,
+    /// shifting older archives up to `app.log.<max_files>`.
+    Size { max_bytes: u64, max_files: usize },
+    /// Start a new file at every period boundary, e.g. `api-{date}.log` becomes
+    /// `api-2026-10-18.log` for [`RotationPeriod::Daily`] or `api-2026-10-18T13.log`
+    /// for [`RotationPeriod::Hourly`]. If the file name has no `{date}` placeholder
+    /// the date is appended as a suffix.
+    Time {
+        period: RotationPeriod,
+        timezone: RotationTimezone,
+    },
+}
+
+impl Default for FileRotation {
+    fn default() -> Self {
+        FileRotation::Never
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationPeriod {
+    Hourly,
+    Daily,
+}
+
+impl RotationPeriod {
+    fn date_format(&self) -> &'static str {
+        match self {
+            RotationPeriod::Hourly => "%Y-%m-%dT%H",
+            RotationPeriod::Daily => "%Y-%m-%d",
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        match self {
+            RotationPeriod::Hourly => Duration::hours(1),
+            RotationPeriod::Daily => Duration::days(1),
+        }
+    }
+
+    fn truncate(&self, local: NaiveDateTime) -> NaiveDateTime {
+        let hour = match self {
+            RotationPeriod::Hourly => local.hour(),
+            RotationPeriod::Daily => 0,
+        };
+        local.date().and_hms_opt(hour, 0, 0).unwrap_or(local)
+    }
+}
+
+/// Wall clock used to decide period boundaries and to render `{date}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationTimezone {
+    Utc,
+    Local,
+}
+
+impl FileRotation {
+    /// Render `{date}`, `{hostname}` and `{pid}` in the file name pattern.
+    pub(crate) fn render_path(&self, pattern: &Path, now: DateTime<Utc>) -> PathBuf {
+        let mut path = match render_process_placeholders(pattern) {
+            Some(path) => path,
+            None => return pattern.to_path_buf(),
+        };
+        if let FileRotation::Time { period, timezone } = self {
+            let date = match timezone {
+                RotationTimezone::Utc => now.format(period.date_format()).to_string(),
+                RotationTimezone::Local => now
+                    .with_timezone(&Local)
+                    .format(period.date_format())
+                    .to_string(),
+            };
+            if path.contains(DATE_PLACEHOLDER) {
+                path = path.replace(DATE_PLACEHOLDER, &date);
+            } else {
+                path = format!("{}.{}", path, date);
+            }
+        }
+        PathBuf::from(path)
+    }
+
+    /// The instant at which a file opened at `now` has to be rolled over.
+    pub(crate) fn next_rollover(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            FileRotation::Time { period, timezone } => Some(match timezone {
+                RotationTimezone::Utc => next_boundary(&Utc, *period, now),
+                RotationTimezone::Local => next_boundary(&Local, *period, now),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Split the file names of archives rotated out of `pattern` into the directory they
+/// live in plus the prefix and suffix surrounding the part that varies.
+pub(crate) fn archive_name_parts(
+    pattern: &Path,
+    rotation: FileRotation,
+) -> Option<(PathBuf, String, String)> {
+    let path = PathBuf::from(render_process_placeholders(pattern)?);
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let file_name = path.file_name()?.to_str()?;
+    match rotation {
+        FileRotation::Never => None,
+        FileRotation::Time { .. } if file_name.contains(DATE_PLACEHOLDER) => {
+            let mut parts = file_name.splitn(2, DATE_PLACEHOLDER);
+            let prefix = parts.next().unwrap_or("").to_owned();
+            let suffix = parts.next().unwrap_or("").to_owned();
+            Some((dir, prefix, suffix))
+        }
+        _ => Some((dir, format!("{}.", file_name), "".to_owned())),
+    }
+}
+
+fn render_process_placeholders(pattern: &Path) -> Option<String> {
+    let pattern = pattern.to_str()?;
+    Some(
+        pattern
+            .replace(
+                HOSTNAME_PLACEHOLDER,
+                crate::metadata::hostname().unwrap_or("localhost"),
+            )
+            .replace(PID_PLACEHOLDER, &process::id().to_string()),
+    )
+}
+
+fn next_boundary<Tz: TimeZone>(
+    tz: &Tz,
+    period: RotationPeriod,
+    now: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let start = period.truncate(now.with_timezone(tz).naive_local());
+    tz.from_local_datetime(&(start + period.duration()))
+        .earliest()
+        .map(|boundary| boundary.with_timezone(&Utc))
+        // the boundary falls into a DST gap, just wait for a whole period
+        .unwrap_or_else(|| now + period.duration())
+}
+
+/// Shift `path.1 .. path.<max_files - 1>` up by one and move `path` to `path.1`.
+/// The oldest archive is removed so at most `max_files` archives are kept.
+/// Compressed archives (`path.2.gz`, ...) are shifted along with the plain ones.
+pub(crate) fn rotate_numbered(path: &Path, max_files: usize) -> io::Result<()> {
+    if max_files == 0 {
+        return remove_if_exists(path);
+    }
+    shift_numbered(path, max_files)?;
+    fs::rename(path, archive_path(path, 1))
+}
+
+/// Make room for a new `path.1` by shifting the archives of `path` up by one.
+pub(crate) fn shift_numbered(path: &Path, max_files: usize) -> io::Result<()> {
+    for extension in ARCHIVE_EXTENSIONS {
+        remove_if_exists(&with_extension(&archive_path(path, max_files), extension))?;
+    }
+    for index in (1..max_files).rev() {
+        for extension in ARCHIVE_EXTENSIONS {
+            let from = with_extension(&archive_path(path, index), extension);
+            if from.exists() {
+                fs::rename(
+                    &from,
+                    with_extension(&archive_path(path, index + 1), extension),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A fresh name next to `path` for a file which has been rotated out but not yet
+/// numbered, hidden so that it is never taken for an archive. The names sort in
+/// rotation order.
+pub(crate) fn staging_path(path: &Path) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let mut staged = staging_prefix(path);
+    staged.push(format!("{:030}", nanos));
+    let mut staged = PathBuf::from(staged);
+    while staged.exists() {
+        let mut next = staged.into_os_string();
+        next.push("0");
+        staged = PathBuf::from(next);
+    }
+    staged
+}
+
+/// `dir/.app.log.rotated-` for `dir/app.log`, what every staging path of it starts with.
+pub(crate) fn staging_prefix(path: &Path) -> OsString {
+    let mut prefix = path
+        .parent()
+        .map(|dir| dir.as_os_str().to_owned())
+        .unwrap_or_default();
+    if !prefix.is_empty() {
+        prefix.push(std::path::MAIN_SEPARATOR.to_string());
+    }
+    prefix.push(".");
+    prefix.push(path.file_name().unwrap_or_default());
+    prefix.push(".rotated-");
+    prefix
+}
+
+pub(crate) fn archive_path(path: &Path, index: usize) -> PathBuf {
+    with_extension(path, &format!(".{}", index))
+}
+
+fn with_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut file_name = path.as_os_str().to_owned();
+    file_name.push(extension);
+    PathBuf::from(file_name)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::{FileWriter, Writer};
+    use chrono::FixedOffset;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("logger-rotation-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn utc(rfc3339: &str) -> DateTime<Utc> {
+        rfc3339.parse().unwrap()
+    }
+
+    fn daily_utc() -> FileRotation {
+        FileRotation::Time {
+            period: RotationPeriod::Daily,
+            timezone: RotationTimezone::Utc,
+        }
+    }
+
+    #[test]
+    fn size_rollover_keeps_max_files_archives() {
+        let dir = temp_dir("size");
+        let path = dir.join("app.log");
+        let writer = FileWriter::with_rotation(
+            path.clone(),
+            FileRotation::Size {
+                max_bytes: 12,
+                max_files: 2,
+            },
+        );
+        for log in [
+            "aaaaa", "bbbbb", "ccccc", "ddddd", "eeeee", "fffff", "ggggg",
+        ] {
+            writer.write(log.to_owned());
+        }
+        drop(writer);
+
+        let read = |path: PathBuf| fs::read_to_string(path).unwrap();
+        assert_eq!(read(path.clone()), "ggggg\n");
+        assert_eq!(read(archive_path(&path, 1)), "eeeee\nfffff\n");
+        assert_eq!(read(archive_path(&path, 2)), "ccccc\nddddd\n");
+        assert!(!archive_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn rotate_numbered_shifts_plain_and_compressed_archives() {
+        let dir = temp_dir("numbered");
+        let path = dir.join("app.log");
+        fs::write(&path, "active").unwrap();
+        fs::write(archive_path(&path, 1), "one").unwrap();
+        fs::write(with_extension(&archive_path(&path, 2), ".gz"), "two").unwrap();
+
+        rotate_numbered(&path, 3).unwrap();
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(archive_path(&path, 1)).unwrap(),
+            "active"
+        );
+        assert_eq!(fs::read_to_string(archive_path(&path, 2)).unwrap(), "one");
+        let oldest = with_extension(&archive_path(&path, 3), ".gz");
+        assert_eq!(fs::read_to_string(&oldest).unwrap(), "two");
+
+        // the archive at `max_files` is removed to make room.
+        fs::write(&path, "next").unwrap();
+        rotate_numbered(&path, 3).unwrap();
+        assert!(!oldest.exists());
+        assert_eq!(fs::read_to_string(archive_path(&path, 1)).unwrap(), "next");
+        assert_eq!(fs::read_to_string(archive_path(&path, 3)).unwrap(), "one");
+    }
+
+    #[test]
+    fn shift_numbered_leaves_index_one_free() {
+        let dir = temp_dir("shift");
+        let path = dir.join("app.log");
+        fs::write(archive_path(&path, 1), "one").unwrap();
+        fs::write(archive_path(&path, 2), "two").unwrap();
+
+        shift_numbered(&path, 2).unwrap();
+        assert!(!archive_path(&path, 1).exists());
+        assert_eq!(fs::read_to_string(archive_path(&path, 2)).unwrap(), "one");
+    }
+
+    #[test]
+    fn zero_max_files_removes_the_file() {
+        let dir = temp_dir("zero");
+        let path = dir.join("app.log");
+        fs::write(&path, "active").unwrap();
+
+        rotate_numbered(&path, 0).unwrap();
+        assert!(!path.exists());
+        assert!(!archive_path(&path, 1).exists());
+    }
+
+    #[test]
+    fn next_boundary_in_utc() {
+        let now = utc("2026-10-18T13:25:10Z");
+        assert_eq!(
+            next_boundary(&Utc, RotationPeriod::Hourly, now),
+            utc("2026-10-18T14:00:00Z")
+        );
+        assert_eq!(
+            next_boundary(&Utc, RotationPeriod::Daily, now),
+            utc("2026-10-19T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn next_boundary_follows_the_timezone() {
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        // already the 19th in Tokyo, the next day starts there at 15:00 UTC on the 19th.
+        let now = utc("2026-10-18T16:30:00Z");
+        assert_eq!(
+            next_boundary(&tokyo, RotationPeriod::Daily, now),
+            utc("2026-10-19T15:00:00Z")
+        );
+
+        let half_hour = FixedOffset::west_opt(3 * 3600 + 1800).unwrap();
+        assert_eq!(
+            next_boundary(&half_hour, RotationPeriod::Hourly, now),
+            utc("2026-10-18T17:00:00Z") + Duration::minutes(30)
+        );
+    }
+
+    #[test]
+    fn next_rollover_only_for_time_rotation() {
+        let now = utc("2026-10-18T13:25:10Z");
+        assert_eq!(
+            daily_utc().next_rollover(now),
+            Some(utc("2026-10-19T00:00:00Z"))
+        );
+        assert_eq!(FileRotation::Never.next_rollover(now), None);
+    }
+
+    #[test]
+    fn render_path_replaces_the_date_placeholder() {
+        let now = utc("2026-10-18T13:25:10Z");
+        assert_eq!(
+            daily_utc().render_path(Path::new("logs/api-{date}.log"), now),
+            PathBuf::from("logs/api-2026-10-18.log")
+        );
+        let hourly = FileRotation::Time {
+            period: RotationPeriod::Hourly,
+            timezone: RotationTimezone::Utc,
+        };
+        assert_eq!(
+            hourly.render_path(Path::new("api-{date}.log"), now),
+            PathBuf::from("api-2026-10-18T13.log")
+        );
+        // without a placeholder the date becomes a suffix.
+        assert_eq!(
+            daily_utc().render_path(Path::new("app.log"), now),
+            PathBuf::from("app.log.2026-10-18")
+        );
+    }
+
+    #[test]
+    fn render_path_replaces_process_placeholders() {
+        let now = utc("2026-10-18T13:25:10Z");
+        let expected = format!(
+            "{}-{}.log",
+            crate::metadata::hostname().unwrap_or("localhost"),
+            process::id()
+        );
+        assert_eq!(
+            FileRotation::Never.render_path(Path::new("{hostname}-{pid}.log"), now),
+            PathBuf::from(&expected)
+        );
+        // `{date}` is only rendered when rotating by time.
+        assert_eq!(
+            FileRotation::Never.render_path(Path::new("app-{date}.log"), now),
+            PathBuf::from("app-{date}.log")
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use signal_hook::{consts::SIGHUP, low_level, SigId};
+use std::{
+    io,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+/// Raises a flag whenever SIGHUP arrives, for as long as it is alive.
+pub(crate) struct SighupRegistration(SigId);
+
+impl SighupRegistration {
+    pub(crate) fn register(flag: Arc<AtomicBool>) -> io::Result<Self> {
+        signal_hook::flag::register(SIGHUP, flag).map(Self)
+    }
+}
+
+impl Drop for SighupRegistration {
+    fn drop(&mut self) {
+        low_level::unregister(self.0);
+    }
+}
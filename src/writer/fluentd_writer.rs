@@ -0,0 +1,433 @@
+use super::{tcp_writer::Backoff, ReconnectPolicy, Writer};
+use serde::Deserialize;
+use std::{
+    collections::{hash_map::RandomState, VecDeque},
+    hash::{BuildHasher, Hasher},
+    io::{self, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+const WRITE_TIMEOUT_MS: u64 = 2000;
+const CONNECTION_TIMEOUT_MS: u64 = 5000;
+/// Extension type of EventTime, seconds and nanoseconds as two big-endian u32.
+const EVENT_TIME_EXT_TYPE: i8 = 0;
+/// Entries of one tag sent in a single PackedForward message at most.
+const MAX_MESSAGE_BYTES: usize = 1024 * 1024;
+
+pub struct FluentdWriterBuilder {
+    endpoint: String,
+    reconnect: ReconnectPolicy,
+    connect_timeout: Duration,
+    write_timeout: Duration,
+    ack_timeout: Option<Duration>,
+    max_pending_bytes: usize,
+}
+
+impl FluentdWriterBuilder {
+    pub fn new(endpoint: String) -> Self {
+        Self {
+            endpoint,
+            reconnect: ReconnectPolicy::default(),
+            connect_timeout: Duration::from_millis(CONNECTION_TIMEOUT_MS),
+            write_timeout: Duration::from_millis(WRITE_TIMEOUT_MS),
+            ack_timeout: None,
+            max_pending_bytes: 1024 * 1024,
+        }
+    }
+
+    /// Back off between failed connection attempts, see [`ReconnectPolicy`].
+    pub fn reconnect(&mut self, reconnect: ReconnectPolicy) -> &mut Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Give up connecting to a single resolved address after this long.
+    pub fn connect_timeout(&mut self, connect_timeout: Duration) -> &mut Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Give up sending a message after this long.
+    pub fn write_timeout(&mut self, write_timeout: Duration) -> &mut Self {
+        self.write_timeout = write_timeout;
+        self
+    }
+
+    /// Ask the server to acknowledge every message with the `chunk` option and send it
+    /// again, over a new connection, if no acknowledgement arrives within `ack_timeout`.
+    /// Logs are then delivered at least once, a log may be received twice.
+    pub fn require_ack(&mut self, ack_timeout: Duration) -> &mut Self {
+        self.ack_timeout = Some(ack_timeout);
+        self
+    }
+
+    /// How many bytes of logs are kept while the server is unreachable, logs beyond that
+    /// are dropped and counted by [`FluentdWriter::dropped_events`].
+    pub fn max_pending_bytes(&mut self, max_pending_bytes: usize) -> &mut Self {
+        self.max_pending_bytes = max_pending_bytes;
+        self
+    }
+
+    /// Connecting is deferred to the first write.
+    pub fn build(&mut self) -> FluentdWriter {
+        let connection = Connection {
+            endpoint: self.endpoint.clone(),
+            stream: None,
+            next_connection_attempt: None,
+            backoff: Backoff::new(self.reconnect),
+            connect_timeout: self.connect_timeout,
+            write_timeout: self.write_timeout,
+            ack_timeout: self.ack_timeout,
+        };
+        FluentdWriter {
+            inner: Mutex::new(Inner {
+                connection,
+                pending: VecDeque::new(),
+                pending_bytes: 0,
+                is_failing: false,
+            }),
+            max_pending_bytes: self.max_pending_bytes,
+            dropped_events: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Sends entries formatted by [`FluentdFormatter`](crate::FluentdFormatter) to Fluentd or
+/// Fluent Bit over the Forward protocol.
+///
+/// Entries are sent as PackedForward messages, one per run of entries sharing a tag. Entries
+/// which could not be sent are kept and sent together once reconnected.
+pub struct FluentdWriter {
+    inner: Mutex<Inner>,
+    max_pending_bytes: usize,
+    dropped_events: AtomicU64,
+}
+
+struct Inner {
+    connection: Connection,
+    /// Entries waiting to be sent, oldest first.
+    pending: VecDeque<Entry>,
+    pending_bytes: usize,
+    is_failing: bool,
+}
+
+/// An entry encoded as the MessagePack `[time, record]` pair of the Forward protocol.
+struct Entry {
+    tag: String,
+    bytes: Vec<u8>,
+}
+
+/// The json object made by [`FluentdFormatter`](crate::FluentdFormatter).
+#[derive(Deserialize)]
+struct FormattedEntry {
+    tag: String,
+    /// Seconds since the epoch, negative before 1970, and nanoseconds.
+    time: (i64, u32),
+    record: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct AckResponse {
+    ack: String,
+}
+
+impl FluentdWriter {
+    pub fn builder(endpoint: String) -> FluentdWriterBuilder {
+        FluentdWriterBuilder::new(endpoint)
+    }
+
+    pub fn new(endpoint: String) -> Self {
+        FluentdWriterBuilder::new(endpoint).build()
+    }
+
+    /// Number of logs which could neither be sent nor kept until reconnected.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped_events.load(Ordering::Relaxed)
+    }
+
+    pub fn flush(&self) {
+        let mut inner = self.inner.lock().expect("fluentd lock must be handled...");
+        if let Err(e) = inner.send_pending().and_then(|_| inner.connection.flush()) {
+            eprintln!(
+                "[Logging] Error while flushing data to fluentd endpoint({}): {}",
+                inner.connection.endpoint, e
+            );
+        }
+    }
+}
+
+impl Writer for FluentdWriter {
+    fn write(&self, log: String) {
+        let entry = match encode_entry(&log) {
+            Ok(entry) => entry,
+            Err(e) => {
+                eprintln!("[Logging] Unable to encode fluentd entry: {}", e);
+                return;
+            }
+        };
+        let mut inner = self.inner.lock().expect("fluentd lock must be handled...");
+        if inner.pending_bytes + entry.bytes.len() <= self.max_pending_bytes {
+            inner.pending_bytes += entry.bytes.len();
+            inner.pending.push_back(entry);
+        } else {
+            self.dropped_events.fetch_add(1, Ordering::Relaxed);
+        }
+
+        match inner.send_pending() {
+            Ok(()) => inner.is_failing = false,
+            Err(e) => {
+                // only report the first failure until the logs get through again.
+                if !inner.is_failing {
+                    eprintln!(
+                        "[Logging] Error while sending data to fluentd endpoint({}), keeping logs until reconnected: {}",
+                        inner.connection.endpoint, e
+                    );
+                }
+                inner.is_failing = true;
+            }
+        }
+    }
+}
+
+impl Inner {
+    /// Send the pending entries in order until one message fails, removing the sent ones.
+    fn send_pending(&mut self) -> io::Result<()> {
+        while let Some(first) = self.pending.front() {
+            let mut count = 0;
+            let mut bytes = 0;
+            for entry in self
+                .pending
+                .iter()
+                .take_while(|entry| entry.tag == first.tag)
+            {
+                if count > 0 && bytes + entry.bytes.len() > MAX_MESSAGE_BYTES {
+                    break;
+                }
+                count += 1;
+                bytes += entry.bytes.len();
+            }
+            let chunk = self.connection.ack_timeout.map(|_| chunk_id());
+            let message = encode_message(
+                &first.tag,
+                self.pending.iter().take(count),
+                count,
+                chunk.as_deref(),
+            )?;
+            self.connection.send(&message, chunk.as_deref())?;
+            self.pending_bytes -= bytes;
+            self.pending.drain(..count);
+        }
+        Ok(())
+    }
+}
+
+struct Connection {
+    endpoint: String,
+    stream: Option<TcpStream>,
+    next_connection_attempt: Option<Instant>,
+    backoff: Backoff,
+    connect_timeout: Duration,
+    write_timeout: Duration,
+    ack_timeout: Option<Duration>,
+}
+
+impl Connection {
+    fn connect(&self) -> io::Result<TcpStream> {
+        let mut last_error = io::Error::new(
+            io::ErrorKind::Other,
+            format!("Unable to resolve and connect to {}", self.endpoint),
+        );
+        for socket_address in self.endpoint.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&socket_address, self.connect_timeout) {
+                Ok(stream) => {
+                    stream.set_write_timeout(Some(self.write_timeout))?;
+                    stream.set_read_timeout(self.ack_timeout)?;
+                    return Ok(stream);
+                }
+                Err(err) => last_error = err,
+            }
+        }
+        Err(last_error)
+    }
+
+    fn stream(&mut self) -> io::Result<&mut TcpStream> {
+        if self.stream.is_none() {
+            // only reconnect once the backoff delay has passed.
+            if let Some(next_connection_attempt) = self.next_connection_attempt {
+                if Instant::now() < next_connection_attempt {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "unable to refresh connection",
+                    ));
+                }
+            }
+            match self.connect() {
+                Ok(stream) => {
+                    self.stream = Some(stream);
+                    self.next_connection_attempt = None;
+                    self.backoff.reset();
+                }
+                Err(e) => {
+                    eprintln!("[Logging] Failed to connect: {}", e);
+                    self.next_connection_attempt = Some(self.backoff.next_attempt());
+                    return Err(e);
+                }
+            }
+        }
+        self.stream
+            .as_mut()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "No stream"))
+    }
+
+    /// Write `message` and wait for the acknowledgement of `chunk`, if any. The connection
+    /// is dropped on failure as its state is unknown.
+    fn send(&mut self, message: &[u8], chunk: Option<&str>) -> io::Result<()> {
+        let result = self.stream().and_then(|stream| {
+            stream.write_all(message)?;
+            match chunk {
+                Some(chunk) => {
+                    let response: AckResponse = rmp_serde::from_read(&mut *stream)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                    if response.ack == chunk {
+                        Ok(())
+                    } else {
+                        Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("expected ack of {}, got {}", chunk, response.ack),
+                        ))
+                    }
+                }
+                None => Ok(()),
+            }
+        });
+        if result.is_err() {
+            self.stream = None;
+        }
+        result
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.stream.as_mut() {
+            Some(stream) => stream.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse the json of an entry and encode it as `[EventTime, record]`.
+fn encode_entry(log: &str) -> io::Result<Entry> {
+    let entry: FormattedEntry =
+        serde_json::from_str(log).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut bytes = Vec::with_capacity(log.len());
+    rmp::encode::write_array_len(&mut bytes, 2)?;
+    let (seconds, nanoseconds) = entry.time;
+    match u32::try_from(seconds) {
+        Ok(seconds) => {
+            rmp::encode::write_ext_meta(&mut bytes, 8, EVENT_TIME_EXT_TYPE)?;
+            bytes.extend_from_slice(&seconds.to_be_bytes());
+            bytes.extend_from_slice(&nanoseconds.to_be_bytes());
+        }
+        // EventTime has unsigned seconds, other times are sent as whole seconds.
+        Err(_) => {
+            rmp::encode::write_sint(&mut bytes, seconds)?;
+        }
+    }
+    rmp_serde::encode::write(&mut bytes, &entry.record)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(Entry {
+        tag: entry.tag,
+        bytes,
+    })
+}
+
+/// Encode the PackedForward message `[tag, entries, option]`.
+fn encode_message<'a, I>(
+    tag: &str,
+    entries: I,
+    count: usize,
+    chunk: Option<&str>,
+) -> io::Result<Vec<u8>>
+where
+    I: Iterator<Item = &'a Entry> + Clone,
+{
+    let len: usize = entries.clone().map(|entry| entry.bytes.len()).sum();
+    let mut message = Vec::with_capacity(len + tag.len() + 64);
+    rmp::encode::write_array_len(&mut message, 3)?;
+    rmp::encode::write_str(&mut message, tag)?;
+    rmp::encode::write_bin_len(&mut message, len as u32)?;
+    for entry in entries {
+        message.extend_from_slice(&entry.bytes);
+    }
+    rmp::encode::write_map_len(&mut message, if chunk.is_some() { 2 } else { 1 })?;
+    rmp::encode::write_str(&mut message, "size")?;
+    rmp::encode::write_uint(&mut message, count as u64)?;
+    if let Some(chunk) = chunk {
+        rmp::encode::write_str(&mut message, "chunk")?;
+        rmp::encode::write_str(&mut message, chunk)?;
+    }
+    Ok(message)
+}
+
+/// A 128 bit random id, base64 encoded as the protocol recommends.
+fn chunk_id() -> String {
+    // every RandomState is seeded differently, per process and per call.
+    let mut id = [0u8; 16];
+    id[..8].copy_from_slice(&RandomState::new().build_hasher().finish().to_be_bytes());
+    id[8..].copy_from_slice(&RandomState::new().build_hasher().finish().to_be_bytes());
+    base64::encode(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `{"msg":"hi"}` as MessagePack.
+    const RECORD: &[u8] = b"\x81\xa3msg\xa2hi";
+
+    #[test]
+    fn entry_time_is_an_event_time() {
+        let entry = encode_entry(
+            r#"{"tag":"app.info","time":[1700000000,123456789],"record":{"msg":"hi"}}"#,
+        )
+        .unwrap();
+
+        // fixext 8 of type 0, then seconds and nanoseconds as big-endian u32.
+        let mut expected = b"\x92\xd7\x00".to_vec();
+        expected.extend_from_slice(&1_700_000_000u32.to_be_bytes());
+        expected.extend_from_slice(&123_456_789u32.to_be_bytes());
+        expected.extend_from_slice(RECORD);
+        assert_eq!(entry.tag, "app.info");
+        assert_eq!(entry.bytes, expected);
+    }
+
+    #[test]
+    fn times_before_1970_are_whole_seconds() {
+        let entry =
+            encode_entry(r#"{"tag":"app","time":[-86401,500000000],"record":{"msg":"hi"}}"#)
+                .unwrap();
+
+        let mut expected = b"\x92\xd2".to_vec();
+        expected.extend_from_slice(&(-86401i32).to_be_bytes());
+        expected.extend_from_slice(RECORD);
+        assert_eq!(entry.bytes, expected);
+    }
+
+    #[test]
+    fn packed_forward_message() {
+        let entry = encode_entry(r#"{"tag":"app","time":[1,2],"record":{"msg":"hi"}}"#).unwrap();
+        let message = encode_message("app", [&entry, &entry].into_iter(), 2, Some("abc")).unwrap();
+
+        let entry_bytes: &[u8] = b"\x92\xd7\x00\x00\x00\x00\x01\x00\x00\x00\x02\x81\xa3msg\xa2hi";
+        let mut expected = b"\x93\xa3app\xc4".to_vec();
+        expected.push(2 * entry_bytes.len() as u8);
+        expected.extend_from_slice(entry_bytes);
+        expected.extend_from_slice(entry_bytes);
+        expected.extend_from_slice(b"\x82\xa4size\x02\xa5chunk\xa3abc");
+        assert_eq!(message, expected);
+    }
+}
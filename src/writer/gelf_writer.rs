@@ -0,0 +1,314 @@
+use super::{TcpFraming, TcpWriter, TcpWriterBuilder, Writer};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    io,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+};
+
+/// Magic bytes starting every chunk of a chunked GELF message.
+const CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+/// Magic bytes, message id, sequence number and sequence count.
+const CHUNK_HEADER_LEN: usize = 12;
+/// Graylog drops messages split into more chunks than this.
+const MAX_CHUNKS: usize = 128;
+/// Datagram size safe across the internet, larger messages are chunked.
+const DEFAULT_CHUNK_SIZE: usize = 1420;
+
+/// Where a [`GelfWriter`] sends its messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GelfTransport {
+    /// One datagram per message to `host:port`, usually port 12201, chunked when larger
+    /// than the chunk size.
+    Udp(String),
+    /// A connection to `host:port`, every message terminated by a null byte.
+    Tcp(String),
+}
+
+/// Compression of GELF messages sent over udp, Graylog detects it by itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GelfCompression {
+    #[cfg(any(feature = "gzip"))]
+    Gzip,
+    #[cfg(any(feature = "gzip"))]
+    Zlib,
+}
+
+impl GelfCompression {
+    #[allow(unused_variables)]
+    fn encode(&self, message: &[u8]) -> io::Result<Vec<u8>> {
+        match *self {
+            #[cfg(any(feature = "gzip"))]
+            GelfCompression::Gzip => {
+                use std::io::Write;
+                let mut encoder = flate2::write::GzEncoder::new(
+                    Vec::with_capacity(message.len() / 4),
+                    flate2::Compression::default(),
+                );
+                encoder.write_all(message)?;
+                encoder.finish()
+            }
+            #[cfg(any(feature = "gzip"))]
+            GelfCompression::Zlib => {
+                use std::io::Write;
+                let mut encoder = flate2::write::ZlibEncoder::new(
+                    Vec::with_capacity(message.len() / 4),
+                    flate2::Compression::default(),
+                );
+                encoder.write_all(message)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+/// Sends GELF messages, see [`GelfFormatter`](crate::GelfFormatter).
+pub struct GelfWriter {
+    inner: Transport,
+}
+
+enum Transport {
+    Udp {
+        socket: UdpSocket,
+        addr: SocketAddr,
+        chunk_size: usize,
+        compression: Option<GelfCompression>,
+    },
+    Tcp(Box<TcpWriter>),
+}
+
+impl GelfWriter {
+    /// Open the socket of `transport`. Connections are deferred to the first write for
+    /// tcp, the address of a udp endpoint is resolved once here.
+    pub fn new(transport: &GelfTransport) -> io::Result<Self> {
+        let inner = match transport {
+            GelfTransport::Udp(endpoint) => {
+                let addr = endpoint.to_socket_addrs()?.next().ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("{} does not resolve to any address", endpoint),
+                    )
+                })?;
+                let local_addr = if addr.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                Transport::Udp {
+                    socket: UdpSocket::bind(local_addr)?,
+                    addr,
+                    chunk_size: DEFAULT_CHUNK_SIZE,
+                    compression: None,
+                }
+            }
+            GelfTransport::Tcp(endpoint) => {
+                return Self::with_tcp(&mut TcpWriter::builder(endpoint.clone()))
+            }
+        };
+        Ok(Self { inner })
+    }
+
+    /// Send over tcp with the reconnect policy, timeouts and so on of `builder`, messages
+    /// are always terminated by a null byte.
+    pub fn with_tcp(builder: &mut TcpWriterBuilder) -> io::Result<Self> {
+        let writer = builder.framing(TcpFraming::NullDelimited).try_build()?;
+        Ok(Self {
+            inner: Transport::Tcp(Box::new(writer)),
+        })
+    }
+
+    /// Compress messages sent over udp. GELF over tcp does not support compression.
+    pub fn compression(mut self, compression: GelfCompression) -> Self {
+        if let Transport::Udp {
+            compression: current,
+            ..
+        } = &mut self.inner
+        {
+            *current = Some(compression);
+        }
+        self
+    }
+
+    /// Size of the datagrams sent over udp, chunk headers included. Up to 8192 bytes is
+    /// fine within a local network.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        if let Transport::Udp {
+            chunk_size: current,
+            ..
+        } = &mut self.inner
+        {
+            *current = chunk_size.max(CHUNK_HEADER_LEN + 1);
+        }
+        self
+    }
+
+    pub fn flush(&self) {
+        if let Transport::Tcp(writer) = &self.inner {
+            writer.flush()
+        }
+    }
+}
+
+impl Writer for GelfWriter {
+    fn write(&self, log: String) {
+        let result = match &self.inner {
+            Transport::Udp {
+                socket,
+                addr,
+                chunk_size,
+                compression,
+            } => match compression {
+                Some(compression) => compression.encode(log.as_bytes()),
+                None => Ok(log.into_bytes()),
+            }
+            .and_then(|message| send_chunked(socket, addr, &message, *chunk_size)),
+            Transport::Tcp(writer) => {
+                writer.write(log);
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            eprintln!("[Logging] Error while sending data to gelf endpoint: {}", e);
+        }
+    }
+}
+
+/// Send `message` as a single datagram if it fits in `chunk_size`, as GELF chunks otherwise.
+fn send_chunked(
+    socket: &UdpSocket,
+    addr: &SocketAddr,
+    message: &[u8],
+    chunk_size: usize,
+) -> io::Result<()> {
+    if message.len() <= chunk_size {
+        return socket.send_to(message, addr).map(|_| ());
+    }
+    let payload_size = chunk_size - CHUNK_HEADER_LEN;
+    let chunks = message.chunks(payload_size).len();
+    if chunks > MAX_CHUNKS {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "message of {} bytes exceeds {} chunks",
+                message.len(),
+                MAX_CHUNKS
+            ),
+        ));
+    }
+    let message_id = message_id();
+    let mut datagram = Vec::with_capacity(chunk_size);
+    for (sequence, payload) in message.chunks(payload_size).enumerate() {
+        datagram.clear();
+        datagram.extend_from_slice(&CHUNK_MAGIC);
+        datagram.extend_from_slice(&message_id);
+        datagram.push(sequence as u8);
+        datagram.push(chunks as u8);
+        datagram.extend_from_slice(payload);
+        socket.send_to(&datagram, addr)?;
+    }
+    Ok(())
+}
+
+/// Identifies the chunks of one message, only needs to be unique for a few seconds.
+fn message_id() -> [u8; 8] {
+    // every RandomState is seeded differently, per process and per call.
+    RandomState::new().build_hasher().finish().to_be_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn sockets() -> (UdpSocket, UdpSocket, SocketAddr) {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let addr = receiver.local_addr().unwrap();
+        (UdpSocket::bind("127.0.0.1:0").unwrap(), receiver, addr)
+    }
+
+    fn receive(receiver: &UdpSocket, count: usize) -> Vec<Vec<u8>> {
+        let mut buf = [0; 65536];
+        (0..count)
+            .map(|_| {
+                let len = receiver.recv(&mut buf).unwrap();
+                buf[..len].to_vec()
+            })
+            .collect()
+    }
+
+    fn message(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn small_messages_are_not_chunked() {
+        let (socket, receiver, addr) = sockets();
+        let message = message(DEFAULT_CHUNK_SIZE);
+        send_chunked(&socket, &addr, &message, DEFAULT_CHUNK_SIZE).unwrap();
+
+        assert_eq!(receive(&receiver, 1), [message]);
+    }
+
+    #[test]
+    fn chunks_fill_the_chunk_size_and_carry_the_header() {
+        let (socket, receiver, addr) = sockets();
+        let message = message(5000);
+        send_chunked(&socket, &addr, &message, DEFAULT_CHUNK_SIZE).unwrap();
+
+        // 1408 bytes of payload after the 12 byte header of every 1420 byte chunk.
+        let chunks = receive(&receiver, 4);
+        let sizes: Vec<usize> = chunks.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [1420, 1420, 1420, 5000 - 3 * 1408 + 12]);
+        let message_id = &chunks[0][2..10];
+        for (sequence, chunk) in chunks.iter().enumerate() {
+            assert_eq!(chunk[..2], [0x1e, 0x0f]);
+            assert_eq!(&chunk[2..10], message_id);
+            assert_eq!(chunk[10..12], [sequence as u8, 4]);
+        }
+        let payload: Vec<u8> = chunks
+            .iter()
+            .flat_map(|chunk| chunk[12..].to_vec())
+            .collect();
+        assert_eq!(payload, message);
+    }
+
+    #[test]
+    fn messages_are_limited_to_128_chunks() {
+        let (socket, receiver, addr) = sockets();
+        // 8 bytes of payload per chunk.
+        let chunk_size = CHUNK_HEADER_LEN + 8;
+        send_chunked(&socket, &addr, &message(128 * 8), chunk_size).unwrap();
+        let chunks = receive(&receiver, 128);
+        assert_eq!(chunks[127][10..12], [127, 128]);
+
+        let err = send_chunked(&socket, &addr, &message(128 * 8 + 1), chunk_size).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        receiver.set_nonblocking(true).unwrap();
+        assert!(receiver.recv(&mut [0; 64]).is_err(), "nothing is sent");
+    }
+
+    #[test]
+    fn tcp_messages_are_null_terminated() {
+        use std::io::Read;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut builder = TcpWriter::builder(listener.local_addr().unwrap().to_string());
+        // the framing of the builder is replaced.
+        builder
+            .framing(TcpFraming::NewlineDelimited)
+            .connect_timeout(Duration::from_secs(1));
+        let writer = GelfWriter::with_tcp(&mut builder).unwrap();
+        writer.write("{\"a\":1}".to_owned());
+        writer.write("{\"b\":2}".to_owned());
+        writer.flush();
+        drop(writer);
+
+        let mut received = vec![];
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.read_to_end(&mut received).unwrap();
+        assert_eq!(received, b"{\"a\":1}\0{\"b\":2}\0");
+    }
+}
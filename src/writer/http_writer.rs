@@ -1,62 +1,496 @@
 use super::Writer;
-use http::{uri::InvalidUri, StatusCode, Uri};
+use http::{
+    header::{self, HeaderName, HeaderValue},
+    response::Parts,
+    uri::InvalidUri,
+    HeaderMap, Uri,
+};
+use hyper::body::Bytes;
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, SyncSender},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+use tokio::sync::Semaphore;
 
+mod batch;
+pub use batch::{BatchEncoder, BatchFormat, BatchPolicy};
+mod compression;
+pub use compression::HttpCompression;
+mod retry;
+pub use retry::{DeliveryOutcome, DeliveryReport, RetryPolicy};
+#[cfg(any(feature = "elasticsearch"))]
+mod elasticsearch;
+#[cfg(any(feature = "elasticsearch"))]
+pub use elasticsearch::ElasticsearchEncoder;
+#[cfg(any(feature = "loki"))]
+mod loki;
+#[cfg(any(feature = "loki"))]
+pub use loki::LokiEncoder;
+#[cfg(any(feature = "otlp"))]
+mod otlp;
+#[cfg(any(feature = "otlp"))]
+pub use otlp::OtlpProtocol;
+#[cfg(any(feature = "splunk"))]
+mod splunk;
+#[cfg(any(feature = "splunk"))]
+pub use splunk::SplunkEncoder;
+
+/// Logs queued for the worker, and as many again in bodies waiting to be sent.
+const QUEUE_CAPACITY: usize = 10_000;
+const CONTENT_TYPE_JSON: &str = "application/json";
+
+type DeliveryHook = Arc<dyn Fn(&DeliveryReport) + Send + Sync>;
+
+pub struct HttpWriterBuilder {
+    uri: Uri,
+    headers: HeaderMap,
+    connect_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    compression: Option<(HttpCompression, usize)>,
+    batch: Option<BatchPolicy>,
+    encoder: Option<Arc<dyn BatchEncoder>>,
+    retry: RetryPolicy,
+    on_delivery: Option<DeliveryHook>,
+    error: Option<http::Error>,
+}
+
+impl HttpWriterBuilder {
+    pub fn new(uri: Uri) -> Self {
+        Self {
+            uri,
+            headers: HeaderMap::new(),
+            connect_timeout: None,
+            request_timeout: None,
+            compression: None,
+            batch: None,
+            encoder: None,
+            retry: RetryPolicy::default(),
+            on_delivery: None,
+            error: None,
+        }
+    }
+
+    /// Send `name: value` with every request. An invalid name or value
+    /// makes [`try_build`](Self::try_build) fail.
+    pub fn header<K, V>(&mut self, name: K, value: V) -> &mut Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        let header = HeaderName::try_from(name)
+            .map_err(Into::into)
+            .and_then(|name| Ok((name, HeaderValue::try_from(value).map_err(Into::into)?)));
+        match header {
+            Ok((name, mut value)) => {
+                if name == header::AUTHORIZATION {
+                    value.set_sensitive(true);
+                }
+                self.headers.insert(name, value);
+            }
+            Err(err) => self.error = self.error.take().or(Some(err)),
+        }
+        self
+    }
+
+    /// `Authorization: Bearer <token>`.
+    pub fn bearer_auth(&mut self, token: &str) -> &mut Self {
+        self.header(header::AUTHORIZATION, format!("Bearer {}", token))
+    }
+
+    /// `Authorization: Basic <base64 of username:password>`.
+    pub fn basic_auth(&mut self, username: &str, password: Option<&str>) -> &mut Self {
+        let credentials = format!("{}:{}", username, password.unwrap_or(""));
+        self.header(
+            header::AUTHORIZATION,
+            format!("Basic {}", base64::encode(credentials)),
+        )
+    }
+
+    /// Replace the default `Content-Type`, `application/json` or the one of the batch format.
+    pub fn content_type(&mut self, content_type: &str) -> &mut Self {
+        self.header(header::CONTENT_TYPE, content_type)
+    }
+
+    /// Give up connecting to the endpoint after this long.
+    pub fn connect_timeout(&mut self, connect_timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Give up waiting for a response after this long, counting the connection. A request
+    /// timing out is retried like a network error.
+    pub fn request_timeout(&mut self, request_timeout: Duration) -> &mut Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    /// Compress request bodies of at least `min_bytes` bytes and set `Content-Encoding`
+    /// accordingly, smaller ones are not worth the effort.
+    pub fn compression(&mut self, compression: HttpCompression, min_bytes: usize) -> &mut Self {
+        self.compression = Some((compression, min_bytes));
+        self
+    }
+
+    /// Send logs in batches instead of one request per log, see [`BatchPolicy`].
+    /// Pending logs are sent by [`HttpWriter::flush`] and when the writer is dropped.
+    pub fn batch(&mut self, batch: BatchPolicy) -> &mut Self {
+        self.batch = Some(batch);
+        self
+    }
+
+    /// Build request bodies with `encoder` instead of the [`BatchFormat`] of the batch
+    /// policy. Without batching every log is encoded on its own.
+    pub fn encoder<E>(&mut self, encoder: E) -> &mut Self
+    where
+        E: BatchEncoder + 'static,
+    {
+        self.encoder = Some(Arc::new(encoder));
+        self
+    }
+
+    pub fn retry(&mut self, retry: RetryPolicy) -> &mut Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Call `on_delivery` with the outcome of every request, instead of
+    /// printing failed ones to stderr. It runs on the writer's worker thread.
+    pub fn on_delivery<F>(&mut self, on_delivery: F) -> &mut Self
+    where
+        F: Fn(&DeliveryReport) + Send + Sync + 'static,
+    {
+        self.on_delivery = Some(Arc::new(on_delivery));
+        self
+    }
+
+    pub fn build(&mut self) -> HttpWriter {
+        self.try_build().expect("http headers must be valid...")
+    }
+
+    pub fn try_build(&mut self) -> Result<HttpWriter, http::Error> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        let scheme = self
+            .uri
+            .scheme()
+            .map(|scheme| scheme.as_str())
+            .unwrap_or("http");
+        let client = if scheme == "https" {
+            sealed::HttpClient::new_https(self.uri.clone(), self.connect_timeout)
+        } else {
+            sealed::HttpClient::new_http(self.uri.clone(), self.connect_timeout)
+        };
+        let delivery = Delivery {
+            client,
+            headers: self.headers.clone(),
+            request_timeout: self.request_timeout,
+            compression: self.compression,
+            retry: self.retry,
+            on_delivery: self.on_delivery.clone(),
+        };
+        Ok(HttpWriter {
+            sender: spawn_worker(delivery, self.batch, self.encoder.clone()),
+            dropped_events: Arc::new(AtomicU64::new(0)),
+        })
+    }
+}
+
+pub(crate) enum WorkerCommand {
+    Log(String),
+    Flush(SyncSender<()>),
+}
+
+/// Handed from the worker thread to the delivery task, in order.
+pub(crate) enum Outgoing {
+    /// An encoded request body and the number of logs in it.
+    Body(Vec<u8>, usize),
+    Flush(SyncSender<()>),
+}
+
+/// Logs are posted by a worker thread owning one runtime and connection pool for
+/// the lifetime of the writer, so writing never blocks on the endpoint and works
+/// from within async code as well.
 pub struct HttpWriter {
-    inner: sealed::HttpClient,
+    sender: SyncSender<WorkerCommand>,
+    dropped_events: Arc<AtomicU64>,
 }
 
 impl HttpWriter {
+    pub fn builder<U>(url: U) -> Result<HttpWriterBuilder, InvalidUri>
+    where
+        U: TryInto<Uri, Error = InvalidUri>,
+    {
+        Ok(HttpWriterBuilder::new(url.try_into()?))
+    }
+
     pub fn new<U>(url: U) -> Self
     where
         U: TryInto<Uri, Error = InvalidUri>,
     {
-        let url = url.try_into().expect("http endpoint must be valid...");
-        let scheme = url.scheme().map(|scheme| scheme.as_str()).unwrap_or("http");
-        Self {
-            inner: if scheme == "https" {
-                sealed::HttpClient::new_https(url)
-            } else {
-                sealed::HttpClient::new_http(url)
-            },
+        Self::try_new(url).expect("http endpoint must be valid...")
+    }
+
+    pub fn try_new<U>(url: U) -> Result<Self, InvalidUri>
+    where
+        U: TryInto<Uri, Error = InvalidUri>,
+    {
+        Ok(Self::builder(url)?.build())
+    }
+
+    /// Number of logs which could not be queued, because the endpoint fell too far behind.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped_events.load(Ordering::Relaxed)
+    }
+
+    /// Block until every log written so far has been sent.
+    pub fn flush(&self) {
+        let (oneshot_sender, oneshot_receiver) = mpsc::sync_channel(1);
+        if self
+            .sender
+            .send(WorkerCommand::Flush(oneshot_sender))
+            .is_ok()
+        {
+            let _ = oneshot_receiver.recv();
         }
     }
 }
 
 impl Writer for HttpWriter {
     fn write(&self, log: String) {
-        let http_call = async move { self.inner.post(log).await };
-        let res = tokio::runtime::Builder::new_current_thread()
+        if let Err(e) = self.sender.try_send(WorkerCommand::Log(log)) {
+            self.dropped_events.fetch_add(1, Ordering::Relaxed);
+            eprintln!("[Logging] Failed to queue log for http endpoint: {}", e);
+        }
+    }
+}
+
+impl Drop for HttpWriter {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+fn spawn_worker(
+    delivery: Delivery,
+    batch: Option<BatchPolicy>,
+    encoder: Option<Arc<dyn BatchEncoder>>,
+) -> SyncSender<WorkerCommand> {
+    let (sender, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+    thread::spawn(move || {
+        // a worker thread of its own keeps driving pooled connections between requests.
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
             .enable_all()
             .build()
-            .unwrap()
-            .block_on(http_call);
-        match res {
-            Ok(response) => {
-                let response_status = response.status();
-                if response_status != StatusCode::CREATED && response_status != StatusCode::OK {
+            .expect("Unable to start http writer runtime");
+        let encoder = match batch {
+            Some(policy) => Some(encoder.unwrap_or_else(|| Arc::new(policy.format))),
+            None => encoder,
+        };
+        // requests are sent and retried by a task on the runtime, while this thread keeps
+        // taking logs off the queue, so the queue only fills up once as many logs again
+        // are waiting to be sent.
+        let pending_logs = Arc::new(Semaphore::new(QUEUE_CAPACITY));
+        let (outgoing_sender, outgoing_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let delivery_task = runtime.spawn(delivery.run(
+            outgoing_receiver,
+            encoder.clone(),
+            Arc::clone(&pending_logs),
+        ));
+        let send = |outgoing: Outgoing| {
+            if let Outgoing::Body(_, logs) = outgoing {
+                let permits = logs.min(QUEUE_CAPACITY) as u32;
+                if let Ok(permit) = runtime.block_on(pending_logs.acquire_many(permits)) {
+                    // given back by the delivery task once the body has been sent.
+                    permit.forget();
+                }
+            }
+            let _ = outgoing_sender.send(outgoing);
+        };
+        match (batch, encoder.as_deref()) {
+            (Some(policy), Some(encoder)) => batch::run(policy, encoder, receiver, &send),
+            _ => {
+                for command in receiver {
+                    match command {
+                        WorkerCommand::Log(log) => {
+                            let body = match encoder.as_deref() {
+                                Some(encoder) => encoder.encode(&[log]),
+                                None => log.into_bytes(),
+                            };
+                            send(Outgoing::Body(body, 1))
+                        }
+                        WorkerCommand::Flush(done) => send(Outgoing::Flush(done)),
+                    }
+                }
+            }
+        }
+        // send what is left before the runtime goes away.
+        drop(outgoing_sender);
+        let _ = runtime.block_on(delivery_task);
+    });
+    sender
+}
+
+/// Sends request bodies on the worker's runtime.
+struct Delivery {
+    client: sealed::HttpClient,
+    headers: HeaderMap,
+    request_timeout: Option<Duration>,
+    compression: Option<(HttpCompression, usize)>,
+    retry: RetryPolicy,
+    on_delivery: Option<DeliveryHook>,
+}
+
+impl Delivery {
+    /// Post `body` and read the whole response, which also lets the connection be reused.
+    async fn post(&self, body: Bytes, headers: HeaderMap) -> Result<(Parts, Bytes), String> {
+        let exchange = async {
+            let (parts, body) = self.client.post(body, headers).await?.into_parts();
+            Ok::<_, hyper::Error>((parts, hyper::body::to_bytes(body).await?))
+        };
+        match self.request_timeout {
+            Some(request_timeout) => match tokio::time::timeout(request_timeout, exchange).await {
+                Ok(result) => result.map_err(|err| err.to_string()),
+                Err(_) => Err(format!("request timed out after {:?}", request_timeout)),
+            },
+            None => exchange.await.map_err(|err| err.to_string()),
+        }
+    }
+
+    /// Send the bodies handed over by the worker thread one after another, answering a
+    /// flush once the bodies before it have been sent.
+    async fn run(
+        self,
+        mut receiver: tokio::sync::mpsc::UnboundedReceiver<Outgoing>,
+        encoder: Option<Arc<dyn BatchEncoder>>,
+        pending_logs: Arc<Semaphore>,
+    ) {
+        while let Some(outgoing) = receiver.recv().await {
+            match outgoing {
+                Outgoing::Body(body, logs) => {
+                    self.send(body, encoder.as_deref(), logs).await;
+                    pending_logs.add_permits(logs.min(QUEUE_CAPACITY));
+                }
+                // notify the flushing thread that everything written before has been sent.
+                Outgoing::Flush(done) => {
+                    let _ = done.send(());
+                }
+            }
+        }
+    }
+
+    /// Send a body holding `logs` logs, encoded by `encoder` or a single log as is.
+    async fn send(&self, body: Vec<u8>, encoder: Option<&dyn BatchEncoder>, logs: usize) {
+        let content_type = encoder
+            .map(|encoder| encoder.content_type())
+            .unwrap_or(CONTENT_TYPE_JSON);
+        let mut headers = self.headers.clone();
+        if !headers.contains_key(header::CONTENT_TYPE) {
+            headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+        }
+        let body = match self.compression {
+            Some((compression, min_bytes)) if body.len() >= min_bytes => {
+                match compression.encode(&body) {
+                    Ok(compressed) => {
+                        headers.insert(
+                            header::CONTENT_ENCODING,
+                            HeaderValue::from_static(compression.content_encoding()),
+                        );
+                        Bytes::from(compressed)
+                    }
+                    Err(err) => {
+                        eprintln!("[Logging] Unable to compress http request body: {}", err);
+                        Bytes::from(body)
+                    }
+                }
+            }
+            _ => Bytes::from(body),
+        };
+        let mut attempts = 0;
+        let outcome = loop {
+            attempts += 1;
+            let (outcome, retry_after) = match self.post(body.clone(), headers.clone()).await {
+                Ok((response, response_body)) if response.status.is_success() => {
+                    break match encoder.and_then(|encoder| encoder.rejected(&response_body)) {
+                        Some((rejected, reason)) => DeliveryOutcome::PartiallyDelivered {
+                            status: response.status,
+                            rejected,
+                            reason,
+                        },
+                        None => DeliveryOutcome::Delivered(response.status),
+                    };
+                }
+                Ok((response, _)) if !retry::is_retryable(response.status) => {
+                    break DeliveryOutcome::Rejected(response.status)
+                }
+                Ok((response, _)) => (
+                    DeliveryOutcome::Failed(response.status),
+                    retry::retry_after(&response.headers),
+                ),
+                Err(err) => (DeliveryOutcome::Error(err), None),
+            };
+            if attempts > self.retry.max_retries {
+                break outcome;
+            }
+            let delay = retry_after.unwrap_or_else(|| self.retry.delay(attempts));
+            if delay > self.retry.max_delay {
+                break outcome;
+            }
+            tokio::time::sleep(delay).await;
+        };
+
+        let report = DeliveryReport {
+            attempts,
+            logs,
+            outcome,
+        };
+        match self.on_delivery.as_ref() {
+            Some(on_delivery) => on_delivery(&report),
+            None => match &report.outcome {
+                DeliveryOutcome::Delivered(_) => {}
+                DeliveryOutcome::PartiallyDelivered {
+                    rejected, reason, ..
+                } => {
+                    eprintln!(
+                        "[Logging] {} of {} logs rejected by http endpoint: {}",
+                        rejected, report.logs, reason
+                    );
+                }
+                DeliveryOutcome::Rejected(status) | DeliveryOutcome::Failed(status) => {
                     eprintln!(
                         "[Logging] Error response status from http endpoint: {}",
-                        response_status
+                        status
                     );
                 }
-            }
-            Err(err) => {
-                eprintln!(
-                    "[Logging] Error while post log data to http endpoint: {}",
-                    err
-                );
-            }
+                DeliveryOutcome::Error(err) => {
+                    eprintln!(
+                        "[Logging] Error while post log data to http endpoint: {}",
+                        err
+                    );
+                }
+            },
         }
     }
 }
 
 mod sealed {
-    use http::Response;
+    use http::{HeaderMap, Response};
     use hyper::{
+        body::Bytes,
         client::{connect::Connect, HttpConnector},
-        header, Body, Result, Uri,
+        Body, Result, Uri,
     };
     use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+    use std::time::Duration;
 
     #[derive(Debug, Clone)]
     pub struct HyperClient<C> {
@@ -74,15 +508,13 @@ mod sealed {
     where
         C: Connect + Clone + Send + Sync + 'static,
     {
-        pub async fn post(&self, log: String) -> Result<Response<Body>> {
-            let log_bytes = log.into_bytes();
+        pub async fn post(&self, body: Bytes, headers: HeaderMap) -> Result<Response<Body>> {
             let mut request = hyper::Request::builder()
                 .method("POST")
                 .uri(&self.uri)
-                .body(hyper::Body::from(log_bytes))
+                .body(hyper::Body::from(body))
                 .expect("");
-            let headers = request.headers_mut();
-            headers.insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
+            *request.headers_mut() = headers;
             self.inner.request(request).await
         }
     }
@@ -94,24 +526,32 @@ mod sealed {
     }
 
     impl HttpClient {
-        pub fn new_http(uri: Uri) -> Self {
-            Self::Http(HyperClient::new(uri, hyper::Client::new()))
+        pub fn new_http(uri: Uri, connect_timeout: Option<Duration>) -> Self {
+            let mut connector = HttpConnector::new();
+            connector.set_connect_timeout(connect_timeout);
+            Self::Http(HyperClient::new(
+                uri,
+                hyper::Client::builder().build(connector),
+            ))
         }
-        pub fn new_https(uri: Uri) -> Self {
+        pub fn new_https(uri: Uri, connect_timeout: Option<Duration>) -> Self {
+            let mut connector = HttpConnector::new();
+            connector.set_connect_timeout(connect_timeout);
+            connector.enforce_http(false);
             let connector = HttpsConnectorBuilder::new()
                 .with_native_roots()
                 .https_only()
                 .enable_http1()
-                .build();
+                .wrap_connector(connector);
             Self::Https(HyperClient::new(
                 uri,
                 hyper::Client::builder().build(connector),
             ))
         }
-        pub async fn post(&self, log: String) -> Result<Response<Body>> {
+        pub async fn post(&self, body: Bytes, headers: HeaderMap) -> Result<Response<Body>> {
             match self {
-                HttpClient::Http(c) => c.post(log).await,
-                HttpClient::Https(c) => c.post(log).await,
+                HttpClient::Http(c) => c.post(body, headers).await,
+                HttpClient::Https(c) => c.post(body, headers).await,
             }
         }
     }
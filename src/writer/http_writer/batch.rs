@@ -0,0 +1,152 @@
+use super::{Outgoing, WorkerCommand};
+use std::{
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+/// When queued logs are sent by a batching [`HttpWriter`](super::HttpWriter).
+///
+/// A batch is sent as soon as it holds `max_items` logs or `max_bytes` bytes, or once its
+/// oldest log has waited for `max_interval`, whatever comes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchPolicy {
+    pub max_items: usize,
+    pub max_bytes: usize,
+    pub max_interval: Duration,
+    pub format: BatchFormat,
+}
+
+impl Default for BatchPolicy {
+    fn default() -> Self {
+        Self {
+            max_items: 100,
+            max_bytes: 1024 * 1024,
+            max_interval: Duration::from_secs(1),
+            format: BatchFormat::NdJson,
+        }
+    }
+}
+
+/// How the logs of a batch are joined into a request body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchFormat {
+    /// One log per line, sent as `application/x-ndjson`.
+    NdJson,
+    /// A JSON array of the logs, sent as `application/json`.
+    JsonArray,
+}
+
+/// Turns the logs of a batch into one request body, for endpoints which expect
+/// more than a list of documents, see [`HttpWriterBuilder::encoder`](super::HttpWriterBuilder::encoder).
+pub trait BatchEncoder: Send + Sync {
+    fn content_type(&self) -> &'static str;
+    fn encode(&self, logs: &[String]) -> Vec<u8>;
+
+    /// The number of logs refused by the endpoint and the first reason given, read from
+    /// the body of a successful response, for endpoints which report failures per log.
+    fn rejected(&self, _response: &[u8]) -> Option<(usize, String)> {
+        None
+    }
+}
+
+impl BatchEncoder for BatchFormat {
+    fn content_type(&self) -> &'static str {
+        match self {
+            BatchFormat::NdJson => "application/x-ndjson",
+            BatchFormat::JsonArray => "application/json",
+        }
+    }
+
+    fn encode(&self, logs: &[String]) -> Vec<u8> {
+        let bytes: usize = logs.iter().map(|log| log.len()).sum();
+        let mut body = Vec::with_capacity(bytes + logs.len() + 1);
+        match self {
+            BatchFormat::NdJson => {
+                for log in logs {
+                    body.extend_from_slice(log.as_bytes());
+                    body.push(b'\n');
+                }
+            }
+            BatchFormat::JsonArray => {
+                body.push(b'[');
+                for (i, log) in logs.iter().enumerate() {
+                    if i > 0 {
+                        body.push(b',');
+                    }
+                    body.extend_from_slice(log.as_bytes());
+                }
+                body.push(b']');
+            }
+        }
+        body
+    }
+}
+
+#[derive(Default)]
+struct Batch {
+    logs: Vec<String>,
+    bytes: usize,
+    started_at: Option<Instant>,
+}
+
+impl Batch {
+    fn push(&mut self, log: String) {
+        self.started_at.get_or_insert_with(Instant::now);
+        self.bytes += log.len();
+        self.logs.push(log);
+    }
+
+    /// The encoded body and the number of logs in it.
+    fn take(&mut self, encoder: &dyn BatchEncoder) -> Outgoing {
+        let body = encoder.encode(&self.logs);
+        let logs = self.logs.len();
+        *self = Self::default();
+        Outgoing::Body(body, logs)
+    }
+}
+
+/// Collect the logs received on `receiver` into batches and hand each encoded body to `send`
+/// along with the number of logs in it, flushes follow the body of the logs before them.
+/// Returns once every sender is gone, after sending what is left.
+pub(crate) fn run<F>(
+    policy: BatchPolicy,
+    encoder: &dyn BatchEncoder,
+    receiver: Receiver<WorkerCommand>,
+    mut send: F,
+) where
+    F: FnMut(Outgoing),
+{
+    let mut batch = Batch::default();
+    loop {
+        let command = match batch.started_at {
+            Some(started_at) => receiver.recv_timeout(
+                (started_at + policy.max_interval).saturating_duration_since(Instant::now()),
+            ),
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match command {
+            Ok(WorkerCommand::Log(log)) => {
+                if !batch.logs.is_empty() && batch.bytes + log.len() > policy.max_bytes {
+                    send(batch.take(encoder));
+                }
+                batch.push(log);
+                if batch.logs.len() >= policy.max_items || batch.bytes >= policy.max_bytes {
+                    send(batch.take(encoder));
+                }
+            }
+            Ok(WorkerCommand::Flush(done)) => {
+                if !batch.logs.is_empty() {
+                    send(batch.take(encoder));
+                }
+                send(Outgoing::Flush(done));
+            }
+            Err(RecvTimeoutError::Timeout) => send(batch.take(encoder)),
+            Err(RecvTimeoutError::Disconnected) => {
+                if !batch.logs.is_empty() {
+                    send(batch.take(encoder));
+                }
+                return;
+            }
+        }
+    }
+}
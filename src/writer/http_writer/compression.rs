@@ -0,0 +1,40 @@
+use std::io;
+
+/// `Content-Encoding` applied to request bodies, see
+/// [`HttpWriterBuilder::compression`](super::HttpWriterBuilder::compression).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpCompression {
+    #[cfg(any(feature = "gzip"))]
+    Gzip,
+    #[cfg(any(feature = "zstd"))]
+    Zstd,
+}
+
+impl HttpCompression {
+    pub(crate) fn content_encoding(&self) -> &'static str {
+        match *self {
+            #[cfg(any(feature = "gzip"))]
+            HttpCompression::Gzip => "gzip",
+            #[cfg(any(feature = "zstd"))]
+            HttpCompression::Zstd => "zstd",
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub(crate) fn encode(&self, body: &[u8]) -> io::Result<Vec<u8>> {
+        match *self {
+            #[cfg(any(feature = "gzip"))]
+            HttpCompression::Gzip => {
+                use std::io::Write;
+                let mut encoder = flate2::write::GzEncoder::new(
+                    Vec::with_capacity(body.len() / 4),
+                    flate2::Compression::default(),
+                );
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+            #[cfg(any(feature = "zstd"))]
+            HttpCompression::Zstd => zstd::encode_all(body, 0),
+        }
+    }
+}
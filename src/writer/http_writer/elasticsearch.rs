@@ -0,0 +1,55 @@
+use super::{BatchEncoder, BatchFormat};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+#[derive(Deserialize)]
+struct BulkResponse {
+    #[serde(default)]
+    errors: bool,
+    #[serde(default)]
+    items: Vec<Map<String, Value>>,
+}
+
+/// Sends the actions written by [`ElasticsearchFormatter`](crate::ElasticsearchFormatter)
+/// to a `_bulk` endpoint and reads back which of them failed, as the endpoint answers
+/// `200 OK` as long as the request itself is well formed.
+pub struct ElasticsearchEncoder;
+
+impl BatchEncoder for ElasticsearchEncoder {
+    fn content_type(&self) -> &'static str {
+        BatchFormat::NdJson.content_type()
+    }
+
+    fn encode(&self, logs: &[String]) -> Vec<u8> {
+        BatchFormat::NdJson.encode(logs)
+    }
+
+    fn rejected(&self, response: &[u8]) -> Option<(usize, String)> {
+        let response: BulkResponse = match serde_json::from_slice(response) {
+            Ok(response) => response,
+            Err(err) => {
+                eprintln!("[Logging] Unable to parse bulk response: {}", err);
+                return None;
+            }
+        };
+        if !response.errors {
+            return None;
+        }
+        // every item is an object with a single key, the action it reports on.
+        let errors: Vec<&Value> = response
+            .items
+            .iter()
+            .filter_map(|item| item.values().next())
+            .filter_map(|result| result.get("error"))
+            .collect();
+        let reason = match errors.first()? {
+            Value::Object(error) => format!(
+                "{}: {}",
+                error.get("type").and_then(Value::as_str).unwrap_or("error"),
+                error.get("reason").and_then(Value::as_str).unwrap_or("")
+            ),
+            error => error.to_string(),
+        };
+        Some((errors.len(), reason))
+    }
+}
@@ -0,0 +1,42 @@
+use super::BatchEncoder;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize)]
+struct LokiStream {
+    stream: BTreeMap<String, String>,
+    values: Vec<serde_json::Value>,
+}
+
+/// Builds a Loki push request out of the single-event streams written by
+/// [`LokiFormatter`](crate::LokiFormatter), with one stream per distinct set of labels.
+pub struct LokiEncoder;
+
+impl BatchEncoder for LokiEncoder {
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn encode(&self, logs: &[String]) -> Vec<u8> {
+        let mut streams: Vec<LokiStream> = vec![];
+        let mut positions: BTreeMap<BTreeMap<String, String>, usize> = BTreeMap::new();
+        for log in logs {
+            let stream: LokiStream = match serde_json::from_str(log) {
+                Ok(stream) => stream,
+                Err(err) => {
+                    eprintln!("[Logging] Unable to parse loki stream: {}", err);
+                    continue;
+                }
+            };
+            match positions.get(&stream.stream) {
+                Some(&position) => streams[position].values.extend(stream.values),
+                None => {
+                    positions.insert(stream.stream.clone(), streams.len());
+                    streams.push(stream);
+                }
+            }
+        }
+        json!({ "streams": streams }).to_string().into_bytes()
+    }
+}
@@ -0,0 +1,252 @@
+use super::BatchEncoder;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// How [`OtlpFormatter`](crate::OtlpFormatter) records are shipped to an OTLP/HTTP
+/// collector. Records sharing a resource are grouped into one `ResourceLogs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    /// `ExportLogsServiceRequest` in the JSON encoding, sent as `application/json`.
+    HttpJson,
+    /// `ExportLogsServiceRequest` in the binary encoding, sent as `application/x-protobuf`.
+    HttpProtobuf,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Record {
+    resource: Value,
+    log_record: Value,
+}
+
+impl BatchEncoder for OtlpProtocol {
+    fn content_type(&self) -> &'static str {
+        match self {
+            OtlpProtocol::HttpJson => "application/json",
+            OtlpProtocol::HttpProtobuf => "application/x-protobuf",
+        }
+    }
+
+    fn encode(&self, logs: &[String]) -> Vec<u8> {
+        let mut resources: Vec<(Value, Vec<Value>)> = vec![];
+        for log in logs {
+            let record: Record = match serde_json::from_str(log) {
+                Ok(record) => record,
+                Err(err) => {
+                    eprintln!("[Logging] Unable to parse otlp log record: {}", err);
+                    continue;
+                }
+            };
+            match resources.iter_mut().find(|(r, _)| *r == record.resource) {
+                Some((_, records)) => records.push(record.log_record),
+                None => resources.push((record.resource, vec![record.log_record])),
+            }
+        }
+        let scope = json!({
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        });
+        let resource_logs: Vec<Value> = resources
+            .into_iter()
+            .map(|(resource, records)| {
+                json!({
+                    "resource": resource,
+                    "scopeLogs": [{ "scope": scope, "logRecords": records }],
+                })
+            })
+            .collect();
+        let request = json!({ "resourceLogs": resource_logs });
+        match self {
+            OtlpProtocol::HttpJson => request.to_string().into_bytes(),
+            OtlpProtocol::HttpProtobuf => protobuf::export_logs_service_request(&request),
+        }
+    }
+}
+
+/// Just enough of the protobuf wire format to transcode an `ExportLogsServiceRequest`
+/// from the JSON encoding, field numbers are those of `opentelemetry/proto/logs/v1`.
+mod protobuf {
+    use serde_json::Value;
+
+    const VARINT: u64 = 0;
+    const FIXED64: u64 = 1;
+    const LEN: u64 = 2;
+
+    pub(super) fn export_logs_service_request(request: &Value) -> Vec<u8> {
+        let mut buf = vec![];
+        for resource_logs in array(request, "resourceLogs") {
+            message(&mut buf, 1, |buf| encode_resource_logs(buf, resource_logs));
+        }
+        buf
+    }
+
+    fn encode_resource_logs(buf: &mut Vec<u8>, resource_logs: &Value) {
+        message(buf, 1, |buf| {
+            for attribute in array(&resource_logs["resource"], "attributes") {
+                message(buf, 1, |buf| encode_key_value(buf, attribute));
+            }
+        });
+        for scope_logs in array(resource_logs, "scopeLogs") {
+            message(buf, 2, |buf| {
+                message(buf, 1, |buf| {
+                    string(buf, 1, str_field(&scope_logs["scope"], "name"));
+                    string(buf, 2, str_field(&scope_logs["scope"], "version"));
+                });
+                for record in array(scope_logs, "logRecords") {
+                    message(buf, 2, |buf| encode_log_record(buf, record));
+                }
+            });
+        }
+    }
+
+    fn encode_log_record(buf: &mut Vec<u8>, record: &Value) {
+        fixed64(buf, 1, int_field(record, "timeUnixNano") as u64);
+        varint(buf, 2, record["severityNumber"].as_u64().unwrap_or(0));
+        string(buf, 3, str_field(record, "severityText"));
+        message(buf, 5, |buf| encode_any_value(buf, &record["body"]));
+        for attribute in array(record, "attributes") {
+            message(buf, 6, |buf| encode_key_value(buf, attribute));
+        }
+        fixed64(buf, 11, int_field(record, "observedTimeUnixNano") as u64);
+    }
+
+    fn encode_key_value(buf: &mut Vec<u8>, key_value: &Value) {
+        string(buf, 1, str_field(key_value, "key"));
+        message(buf, 2, |buf| encode_any_value(buf, &key_value["value"]));
+    }
+
+    fn encode_any_value(buf: &mut Vec<u8>, value: &Value) {
+        if let Some(value) = value.get("stringValue").and_then(Value::as_str) {
+            string(buf, 1, value);
+        } else if let Some(value) = value.get("boolValue").and_then(Value::as_bool) {
+            varint(buf, 2, u64::from(value));
+        } else if value.get("intValue").is_some() {
+            varint(buf, 3, int_field(value, "intValue") as u64);
+        } else if let Some(value) = value.get("doubleValue").and_then(Value::as_f64) {
+            key(buf, 4, FIXED64);
+            buf.extend_from_slice(&value.to_le_bytes());
+        } else if let Some(values) = value.get("arrayValue") {
+            message(buf, 5, |buf| {
+                for value in array(values, "values") {
+                    message(buf, 1, |buf| encode_any_value(buf, value));
+                }
+            });
+        } else if let Some(values) = value.get("kvlistValue") {
+            message(buf, 6, |buf| {
+                for key_value in array(values, "values") {
+                    message(buf, 1, |buf| encode_key_value(buf, key_value));
+                }
+            });
+        }
+    }
+
+    fn array<'a>(value: &'a Value, field: &str) -> impl Iterator<Item = &'a Value> {
+        value[field].as_array().into_iter().flatten()
+    }
+
+    fn str_field<'a>(value: &'a Value, field: &str) -> &'a str {
+        value[field].as_str().unwrap_or("")
+    }
+
+    /// 64 bit integers are strings in the json encoding, but numbers are accepted too.
+    fn int_field(value: &Value, field: &str) -> i64 {
+        match &value[field] {
+            Value::String(value) => value.parse().unwrap_or(0),
+            value => value.as_i64().unwrap_or(0),
+        }
+    }
+
+    fn key(buf: &mut Vec<u8>, field: u64, wire_type: u64) {
+        write_varint(buf, field << 3 | wire_type);
+    }
+
+    fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            buf.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        buf.push(value as u8);
+    }
+
+    fn varint(buf: &mut Vec<u8>, field: u64, value: u64) {
+        key(buf, field, VARINT);
+        write_varint(buf, value);
+    }
+
+    fn fixed64(buf: &mut Vec<u8>, field: u64, value: u64) {
+        key(buf, field, FIXED64);
+        buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn string(buf: &mut Vec<u8>, field: u64, value: &str) {
+        key(buf, field, LEN);
+        write_varint(buf, value.len() as u64);
+        buf.extend_from_slice(value.as_bytes());
+    }
+
+    /// Write the embedded message built by `encode` as field `field`.
+    fn message<F>(buf: &mut Vec<u8>, field: u64, encode: F)
+    where
+        F: FnOnce(&mut Vec<u8>),
+    {
+        let mut message = vec![];
+        encode(&mut message);
+        key(buf, field, LEN);
+        write_varint(buf, message.len() as u64);
+        buf.extend_from_slice(&message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::protobuf::export_logs_service_request;
+    use serde_json::json;
+
+    #[test]
+    fn protobuf_matches_the_wire_format() {
+        let request = json!({
+            "resourceLogs": [{
+                "resource": {
+                    "attributes": [{ "key": "service.name", "value": { "stringValue": "api" } }],
+                },
+                "scopeLogs": [{
+                    "scope": { "name": "logger", "version": "0.1.0" },
+                    "logRecords": [{
+                        "timeUnixNano": "1700000000000000000",
+                        "observedTimeUnixNano": "1700000000000000001",
+                        "severityNumber": 9,
+                        "severityText": "INFO",
+                        "body": { "stringValue": "hi" },
+                        "attributes": [{ "key": "n", "value": { "intValue": "-1" } }],
+                    }],
+                }],
+            }],
+        });
+        let log_record: &[&[u8]] = &[
+            // time_unix_nano, fixed64
+            b"\x09\x00\x00\x2a\x36\xfe\x9c\x97\x17",
+            // severity_number
+            b"\x10\x09",
+            // severity_text
+            b"\x1a\x04INFO",
+            // body { string_value }
+            b"\x2a\x04\x0a\x02hi",
+            // attributes { key, value { int_value, ten bytes for a negative varint } }
+            b"\x32\x10\x0a\x01n\x12\x0b\x18\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01",
+            // observed_time_unix_nano, fixed64
+            b"\x59\x01\x00\x2a\x36\xfe\x9c\x97\x17",
+        ];
+        let expected: Vec<u8> = [
+            // resource_logs
+            &b"\x0a\x60"[..],
+            // resource { attributes { key, value { string_value } } }
+            b"\x0a\x17\x0a\x15\x0a\x0cservice.name\x12\x05\x0a\x03api",
+            // scope_logs { scope { name, version }, log_records }
+            b"\x12\x45\x0a\x0f\x0a\x06logger\x12\x050.1.0\x12\x32",
+            &log_record.concat(),
+        ]
+        .concat();
+
+        assert_eq!(export_logs_service_request(&request), expected);
+    }
+}
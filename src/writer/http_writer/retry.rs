@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use http::{header, HeaderMap, StatusCode};
+use std::time::Duration;
+
+/// How often and how patiently a request is retried after a network error, a `5xx`
+/// response or `429 Too Many Requests`. Other `4xx` responses are never retried.
+///
+/// The delay starts at `initial_delay` and is multiplied by `multiplier` after every
+/// attempt up to `max_delay`. A `Retry-After` header replaces the computed delay; if it
+/// asks to wait longer than `max_delay` the request is given up instead.
+///
+/// Requests are sent once by default, retries are enabled per writer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl RetryPolicy {
+    /// Send every request once.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+        }
+    }
+
+    /// Retry up to 3 times, half a second after the first attempt and twice as long after
+    /// every other one, up to 30 seconds.
+    pub fn exponential() -> Self {
+        Self {
+            max_retries: 3,
+            ..Self::none()
+        }
+    }
+
+    /// The delay before retry number `retry`, counting from 1.
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let factor = self.multiplier.max(1.0).powi(exponent);
+        let delay = self.initial_delay.as_secs_f64() * factor;
+        // compared as f64 so that an infinite or NaN product ends up at `max_delay` too.
+        if delay < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max_delay
+        }
+    }
+}
+
+/// What became of one request, handed to the hook set with
+/// [`HttpWriterBuilder::on_delivery`](super::HttpWriterBuilder::on_delivery).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryReport {
+    /// Number of requests sent, retries included.
+    pub attempts: u32,
+    /// Number of logs in the request body.
+    pub logs: usize,
+    pub outcome: DeliveryOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    /// The endpoint accepted the logs.
+    Delivered(StatusCode),
+    /// The endpoint accepted the request but refused `rejected` of its logs, which were
+    /// not retried. Only reported for encoders which understand the response, see
+    /// [`BatchEncoder::rejected`](super::BatchEncoder::rejected).
+    PartiallyDelivered {
+        status: StatusCode,
+        rejected: usize,
+        reason: String,
+    },
+    /// The endpoint refused the logs with a client error, they were not retried.
+    Rejected(StatusCode),
+    /// The endpoint kept answering with a server error or `429` until the retries ran out.
+    Failed(StatusCode),
+    /// The last attempt did not get a response at all.
+    Error(String),
+}
+
+impl DeliveryOutcome {
+    pub fn is_delivered(&self) -> bool {
+        matches!(self, DeliveryOutcome::Delivered(_))
+    }
+}
+
+/// Whether a response with `status` is worth another attempt.
+pub(crate) fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// The delay asked for by a `Retry-After` header, either in seconds or as an HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        date.with_timezone(&Utc)
+            .signed_duration_since(Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
@@ -0,0 +1,16 @@
+use super::{BatchEncoder, BatchFormat};
+
+/// Stacks the envelopes written by [`SplunkFormatter`](crate::SplunkFormatter) into one
+/// HTTP Event Collector request, whatever the [`BatchFormat`] of the batch policy.
+pub struct SplunkEncoder;
+
+impl BatchEncoder for SplunkEncoder {
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn encode(&self, logs: &[String]) -> Vec<u8> {
+        // the collector reads consecutive json objects, separated by whitespace or not.
+        BatchFormat::NdJson.encode(logs)
+    }
+}
@@ -0,0 +1,133 @@
+use super::Writer;
+use serde_json::{Map, Value};
+use std::{
+    fs::File,
+    io::{self, Write},
+    mem,
+    os::unix::{
+        io::{AsRawFd, FromRawFd, RawFd},
+        net::UnixDatagram,
+    },
+    path::{Path, PathBuf},
+    ptr,
+};
+
+/// Socket of the native journal protocol.
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+/// Name of the memfds of large entries, only shown in `/proc/<pid>/fd`.
+const MEMFD_NAME: &[u8] = b"logger-journald\0";
+
+/// Sends entries formatted by [`JournaldFormatter`](crate::JournaldFormatter) to
+/// systemd-journald over its native protocol.
+///
+/// Every entry is one datagram. Entries too large for a datagram are written to a sealed
+/// memfd which is passed to journald instead, as `sd_journal_send(3)` does.
+pub struct JournaldWriter {
+    socket: UnixDatagram,
+    path: PathBuf,
+}
+
+impl JournaldWriter {
+    pub fn new() -> io::Result<Self> {
+        Self::with_path(JOURNAL_SOCKET)
+    }
+
+    /// Send to the socket at `path` rather than the one of the system journal.
+    pub fn with_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self {
+            socket: UnixDatagram::unbound()?,
+            path: path.as_ref().to_path_buf(),
+        })
+    }
+
+    fn send(&self, payload: &[u8]) -> io::Result<()> {
+        // sent to the path every time so that a restarted journald is picked up.
+        match self.socket.send_to(payload, &self.path) {
+            Ok(_) => Ok(()),
+            Err(e) if matches!(e.raw_os_error(), Some(libc::EMSGSIZE | libc::ENOBUFS)) => {
+                self.send_memfd(payload)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn send_memfd(&self, payload: &[u8]) -> io::Result<()> {
+        let fd = unsafe {
+            libc::memfd_create(
+                MEMFD_NAME.as_ptr().cast(),
+                libc::MFD_ALLOW_SEALING | libc::MFD_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // owns the descriptor from here on, it is closed once journald has been handed a copy.
+        let mut file = unsafe { File::from_raw_fd(fd) };
+        file.write_all(payload)?;
+        // journald only accepts memfds which can no longer be modified.
+        let seals =
+            libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_WRITE | libc::F_SEAL_SEAL;
+        if unsafe { libc::fcntl(fd, libc::F_ADD_SEALS, seals) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(&self.path)?;
+        send_fd(&socket, file.as_raw_fd())
+    }
+}
+
+impl Writer for JournaldWriter {
+    fn write(&self, log: String) {
+        let result = serialize(&log).and_then(|payload| self.send(&payload));
+        if let Err(e) = result {
+            eprintln!("[Logging] Error while sending data to journald: {}", e);
+        }
+    }
+}
+
+/// Turn the json object of fields of an entry into the native protocol, one `NAME=value`
+/// line per field, or `NAME`, the length as a little endian u64 and the raw value for
+/// values spanning several lines.
+fn serialize(log: &str) -> io::Result<Vec<u8>> {
+    let fields: Map<String, Value> =
+        serde_json::from_str(log).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut payload = Vec::with_capacity(log.len());
+    for (name, value) in &fields {
+        let value = match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        };
+        payload.extend_from_slice(name.as_bytes());
+        if value.contains('\n') {
+            payload.push(b'\n');
+            payload.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        } else {
+            payload.push(b'=');
+        }
+        payload.extend_from_slice(value.as_bytes());
+        payload.push(b'\n');
+    }
+    Ok(payload)
+}
+
+/// Send `fd` alone over the connected `socket` as `SCM_RIGHTS` ancillary data.
+fn send_fd(socket: &UnixDatagram, fd: RawFd) -> io::Result<()> {
+    let fd_len = mem::size_of::<RawFd>() as u32;
+    let space = unsafe { libc::CMSG_SPACE(fd_len) } as usize;
+    // u64 words keep the control buffer aligned for cmsghdr.
+    let mut control = vec![0u64; space / mem::size_of::<u64>() + 1];
+    let mut message: libc::msghdr = unsafe { mem::zeroed() };
+    message.msg_control = control.as_mut_ptr().cast();
+    message.msg_controllen = space as _;
+    unsafe {
+        let header = libc::CMSG_FIRSTHDR(&message);
+        (*header).cmsg_level = libc::SOL_SOCKET;
+        (*header).cmsg_type = libc::SCM_RIGHTS;
+        (*header).cmsg_len = libc::CMSG_LEN(fd_len) as _;
+        ptr::write_unaligned(libc::CMSG_DATA(header).cast::<RawFd>(), fd);
+    }
+    if unsafe { libc::sendmsg(socket.as_raw_fd(), &message, libc::MSG_NOSIGNAL) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
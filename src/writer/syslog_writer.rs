@@ -0,0 +1,93 @@
+use super::{TcpFraming, TcpWriter, Writer};
+use std::{
+    io,
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+};
+#[cfg(unix)]
+use std::{os::unix::net::UnixDatagram, path::PathBuf};
+
+/// Where a [`SyslogWriter`] sends its messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyslogTransport {
+    /// One datagram per message to a local socket such as `/dev/log`.
+    #[cfg(unix)]
+    Unix(PathBuf),
+    /// One datagram per message to `host:port`, usually port 514.
+    Udp(String),
+    /// A connection to `host:port` framed as given, RFC 6587 octet counting being the
+    /// safe choice as messages may contain newlines.
+    Tcp(String, TcpFraming),
+}
+
+impl SyslogTransport {
+    /// The socket of the local syslog daemon.
+    #[cfg(unix)]
+    pub fn local() -> Self {
+        SyslogTransport::Unix(PathBuf::from("/dev/log"))
+    }
+}
+
+/// Sends formatted syslog messages, see [`SyslogFormatter`](crate::SyslogFormatter).
+pub struct SyslogWriter {
+    inner: Transport,
+}
+
+enum Transport {
+    #[cfg(unix)]
+    Unix(UnixDatagram, PathBuf),
+    Udp(UdpSocket, SocketAddr),
+    Tcp(Box<TcpWriter>),
+}
+
+impl SyslogWriter {
+    /// Open the socket of `transport`. Connections are deferred to the first write for
+    /// tcp, the address of a udp endpoint is resolved once here.
+    pub fn new(transport: &SyslogTransport) -> io::Result<Self> {
+        let inner = match transport {
+            #[cfg(unix)]
+            SyslogTransport::Unix(path) => Transport::Unix(UnixDatagram::unbound()?, path.clone()),
+            SyslogTransport::Udp(endpoint) => {
+                let addr = endpoint.to_socket_addrs()?.next().ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("{} does not resolve to any address", endpoint),
+                    )
+                })?;
+                let local_addr = if addr.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                Transport::Udp(UdpSocket::bind(local_addr)?, addr)
+            }
+            SyslogTransport::Tcp(endpoint, framing) => Transport::Tcp(Box::new(
+                TcpWriter::with_framing(endpoint.clone(), *framing),
+            )),
+        };
+        Ok(Self { inner })
+    }
+
+    pub fn flush(&self) {
+        if let Transport::Tcp(writer) = &self.inner {
+            writer.flush()
+        }
+    }
+}
+
+impl Writer for SyslogWriter {
+    fn write(&self, log: String) {
+        let result = match &self.inner {
+            // sent to the path every time so that a restarted daemon is picked up.
+            #[cfg(unix)]
+            Transport::Unix(socket, path) => socket.send_to(log.as_bytes(), path).map(|_| ()),
+            Transport::Udp(socket, addr) => socket.send_to(log.as_bytes(), addr).map(|_| ()),
+            Transport::Tcp(writer) => {
+                writer.write(log);
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            eprintln!("[Logging] Error while sending data to syslog: {}", e);
+        }
+    }
+}
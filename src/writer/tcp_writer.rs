@@ -1,5 +1,8 @@
 use super::Writer;
-use std::sync::RwLock;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex, RwLock,
+};
 use std::{
     io,
     io::Write,
@@ -7,19 +10,173 @@ use std::{
     time::{Duration, Instant},
 };
 
+mod framing;
+pub use framing::TcpFraming;
+mod backlog;
+use backlog::Backlog;
+pub use backlog::BacklogPolicy;
+mod reconnect;
+pub(crate) use reconnect::Backoff;
+pub use reconnect::ReconnectPolicy;
+#[cfg(any(feature = "tls"))]
+mod tls;
+#[cfg(any(feature = "tls"))]
+pub use tls::TcpTlsConfig;
+
 const NUM_SEND_RETRIES: u8 = 1;
 const WRITE_TIMEOUT_MS: u64 = 2000;
 const CONNECTION_TIMEOUT_MS: u64 = 5000;
 
+pub struct TcpWriterBuilder {
+    endpoint: String,
+    framing: TcpFraming,
+    reconnect: ReconnectPolicy,
+    connect_timeout: Duration,
+    write_timeout: Duration,
+    send_retries: u8,
+    backlog: Option<BacklogPolicy>,
+    #[cfg(any(feature = "tls"))]
+    tls: Option<TcpTlsConfig>,
+}
+
+impl TcpWriterBuilder {
+    pub fn new(endpoint: String) -> Self {
+        Self {
+            endpoint,
+            framing: TcpFraming::default(),
+            reconnect: ReconnectPolicy::default(),
+            connect_timeout: Duration::from_millis(CONNECTION_TIMEOUT_MS),
+            write_timeout: Duration::from_millis(WRITE_TIMEOUT_MS),
+            send_retries: NUM_SEND_RETRIES,
+            backlog: None,
+            #[cfg(any(feature = "tls"))]
+            tls: None,
+        }
+    }
+
+    pub fn framing(&mut self, framing: TcpFraming) -> &mut Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Back off between failed connection attempts, see [`ReconnectPolicy`].
+    pub fn reconnect(&mut self, reconnect: ReconnectPolicy) -> &mut Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Give up connecting to a single resolved address after this long.
+    pub fn connect_timeout(&mut self, connect_timeout: Duration) -> &mut Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Give up sending a frame (or completing the TLS handshake) after this long.
+    pub fn write_timeout(&mut self, write_timeout: Duration) -> &mut Self {
+        self.write_timeout = write_timeout;
+        self
+    }
+
+    /// How many more times a frame is sent after the first attempt failed.
+    pub fn send_retries(&mut self, send_retries: u8) -> &mut Self {
+        self.send_retries = send_retries;
+        self
+    }
+
+    /// Keep logs which could not be sent and replay them once reconnected,
+    /// instead of dropping them.
+    pub fn backlog(&mut self, backlog: BacklogPolicy) -> &mut Self {
+        self.backlog = Some(backlog);
+        self
+    }
+
+    /// Send logs over TLS.
+    #[cfg(any(feature = "tls"))]
+    pub fn tls(&mut self, tls: TcpTlsConfig) -> &mut Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    pub fn build(&mut self) -> TcpWriter {
+        self.try_build()
+            .expect("Unable to load tls settings or open spill file")
+    }
+
+    /// Fails if the tls certificates or the spill file cannot be opened,
+    /// connecting is always deferred to the first write.
+    pub fn try_build(&mut self) -> io::Result<TcpWriter> {
+        #[cfg(any(feature = "tls"))]
+        let tls = self
+            .tls
+            .as_ref()
+            .map(|tls| tls::TlsConnector::new(tls, self.endpoint.as_str()))
+            .transpose()?;
+        let handler = TcpStreamHandler {
+            endpoint: self.endpoint.clone(),
+            stream: None,
+            next_connection_attempt: None,
+            backoff: Backoff::new(self.reconnect),
+            connect_timeout: self.connect_timeout,
+            write_timeout: self.write_timeout,
+            #[cfg(any(feature = "tls"))]
+            tls,
+        };
+        let backlog = self
+            .backlog
+            .as_ref()
+            .map(|backlog| Backlog::open(backlog).map(Mutex::new))
+            .transpose()?;
+        Ok(TcpWriter {
+            inner: RwLock::new(handler),
+            framing: self.framing,
+            send_retries: self.send_retries,
+            backlog,
+            dropped_events: Arc::new(AtomicU64::new(0)),
+        })
+    }
+}
+
 pub struct TcpWriter {
     inner: RwLock<TcpStreamHandler>,
+    framing: TcpFraming,
+    send_retries: u8,
+    /// Only locked while holding `inner`.
+    backlog: Option<Mutex<Backlog>>,
+    dropped_events: Arc<AtomicU64>,
 }
 
 impl TcpWriter {
+    pub fn builder(endpoint: String) -> TcpWriterBuilder {
+        TcpWriterBuilder::new(endpoint)
+    }
+
     pub fn new(endpoint: String) -> Self {
-        Self {
-            inner: RwLock::new(TcpStreamHandler::new(endpoint)),
-        }
+        TcpWriterBuilder::new(endpoint).build()
+    }
+
+    pub fn with_framing(endpoint: String, framing: TcpFraming) -> Self {
+        TcpWriterBuilder::new(endpoint).framing(framing).build()
+    }
+
+    /// Send logs over TLS. Fails if the certificates in `tls` cannot be loaded,
+    /// connecting is still deferred to the first write.
+    #[cfg(any(feature = "tls"))]
+    pub fn with_tls(endpoint: String, framing: TcpFraming, tls: &TcpTlsConfig) -> io::Result<Self> {
+        TcpWriterBuilder::new(endpoint)
+            .framing(framing)
+            .tls(tls.clone())
+            .try_build()
+    }
+
+    /// Number of logs which could neither be sent nor kept in the backlog.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped_events.load(Ordering::Relaxed)
+    }
+
+    /// The counter behind [`dropped_events`](Self::dropped_events), readable once the
+    /// writer has been moved to the logger service thread.
+    pub(crate) fn dropped_events_counter(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.dropped_events)
     }
 
     pub fn flush(&self) {
@@ -27,7 +184,12 @@ impl TcpWriter {
             .inner
             .write()
             .expect("tcp stream lock must be handled...");
-        if let Err(e) = inner.flush() {
+        let mut result = Ok(());
+        if let Some(backlog) = self.backlog.as_ref() {
+            let mut backlog = backlog.lock().expect("tcp backlog lock must be handled...");
+            result = backlog.replay(|frame| self.send(&mut inner, frame));
+        }
+        if let Err(e) = result.and_then(|_| inner.flush()) {
             eprintln!(
                 "[Logging] Error while flushing data to tcp endpoint({}): {}",
                 inner.endpoint(),
@@ -35,71 +197,128 @@ impl TcpWriter {
             );
         }
     }
+
+    fn send(&self, inner: &mut TcpStreamHandler, bytes: &[u8]) -> io::Result<()> {
+        let mut result = inner.write_all(bytes);
+        for _ in 0..self.send_retries {
+            if result.is_ok() {
+                break;
+            } else {
+                result = inner.write_all(bytes);
+            }
+        }
+        result
+    }
 }
 
 impl Writer for TcpWriter {
     fn write(&self, log: String) {
-        let bytes = log.as_bytes();
+        let frame = self.framing.frame(&log);
         let mut inner = self
             .inner
             .write()
             .expect("tcp stream lock must be handled...");
-        let mut result = inner.write_all(bytes);
-        for _ in 0..NUM_SEND_RETRIES {
-            if result.is_ok() {
-                break;
-            } else {
-                result = inner.write_all(bytes);
+        let mut backlog = self
+            .backlog
+            .as_ref()
+            .map(|backlog| backlog.lock().expect("tcp backlog lock must be handled..."));
+
+        // older logs go first, the new one joins them if they cannot be sent.
+        let result = match backlog.as_mut() {
+            Some(backlog) if !backlog.is_empty() => {
+                backlog.replay(|frame| self.send(&mut inner, frame))
             }
+            _ => Ok(()),
         }
+        .and_then(|_| self.send(&mut inner, frame.as_slice()));
+
         if let Err(e) = result {
-            eprintln!(
-                "[Logging] Error while sending data to tcp endpoint({}): {}",
-                inner.endpoint(),
-                e
-            );
+            let is_kept = match backlog.as_mut() {
+                Some(backlog) => {
+                    let was_empty = backlog.is_empty();
+                    let is_kept = backlog.push(frame);
+                    if was_empty {
+                        eprintln!(
+                            "[Logging] Error while sending data to tcp endpoint({}), keeping logs until reconnected: {}",
+                            inner.endpoint(),
+                            e
+                        );
+                    }
+                    is_kept
+                }
+                None => {
+                    eprintln!(
+                        "[Logging] Error while sending data to tcp endpoint({}): {}",
+                        inner.endpoint(),
+                        e
+                    );
+                    false
+                }
+            };
+            if !is_kept {
+                self.dropped_events.fetch_add(1, Ordering::Relaxed);
+            }
         }
     }
 }
 
-struct TcpStreamHandler {
-    endpoint: String,
-    stream: Option<TcpStream>,
-    last_connection_attempt: Option<Instant>,
+enum Stream {
+    Plain(TcpStream),
+    #[cfg(any(feature = "tls"))]
+    Tls(Box<tls::TlsStream>),
 }
 
-impl TcpStreamHandler {
-    fn new(endpoint: String) -> Self {
-        Self {
-            endpoint,
-            stream: None,
-            last_connection_attempt: None,
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Plain(stream) => stream.write(buf),
+            #[cfg(any(feature = "tls"))]
+            Stream::Tls(stream) => stream.write(buf),
         }
     }
 
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Plain(stream) => stream.flush(),
+            #[cfg(any(feature = "tls"))]
+            Stream::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+struct TcpStreamHandler {
+    endpoint: String,
+    stream: Option<Stream>,
+    next_connection_attempt: Option<Instant>,
+    backoff: Backoff,
+    connect_timeout: Duration,
+    write_timeout: Duration,
+    #[cfg(any(feature = "tls"))]
+    tls: Option<tls::TlsConnector>,
+}
+
+impl TcpStreamHandler {
     fn endpoint(&self) -> &str {
         self.endpoint.as_str()
     }
 
-    fn connect(&mut self) -> io::Result<TcpStream> {
+    fn connect(&mut self) -> io::Result<Stream> {
         let mut last_error = io::Error::new(
             io::ErrorKind::Other,
             format!("Unable to resolve and connect to {}", self.endpoint),
         );
 
         for socket_address in self.endpoint.to_socket_addrs()? {
-            match TcpStream::connect_timeout(
-                &socket_address,
-                Duration::from_millis(CONNECTION_TIMEOUT_MS),
-            ) {
+            match TcpStream::connect_timeout(&socket_address, self.connect_timeout) {
                 Ok(stream) => {
-                    if let Err(err) =
-                        stream.set_write_timeout(Some(Duration::from_millis(WRITE_TIMEOUT_MS)))
-                    {
+                    if let Err(err) = stream.set_write_timeout(Some(self.write_timeout)) {
                         eprintln!("[Logging] Failed to set write timeout: {}", err);
                         continue;
                     }
-                    return Ok(stream);
+                    match self.wrap(stream) {
+                        Ok(stream) => return Ok(stream),
+                        Err(err) => last_error = err,
+                    }
                 }
                 Err(err) => last_error = err,
             }
@@ -108,21 +327,36 @@ impl TcpStreamHandler {
         Err(last_error)
     }
 
+    #[cfg(any(feature = "tls"))]
+    fn wrap(&self, stream: TcpStream) -> io::Result<Stream> {
+        match self.tls.as_ref() {
+            Some(tls) => Ok(Stream::Tls(Box::new(tls.connect(stream)?))),
+            None => Ok(Stream::Plain(stream)),
+        }
+    }
+
+    #[cfg(not(feature = "tls"))]
+    fn wrap(&self, stream: TcpStream) -> io::Result<Stream> {
+        Ok(Stream::Plain(stream))
+    }
+
     fn refresh_connection(&mut self) -> io::Result<()> {
-        // Only refresh the connection once a second
+        // Only refresh the connection once the backoff delay has passed
         if self
-            .last_connection_attempt
-            .map(|t| t.elapsed() > Duration::from_millis(1000))
+            .next_connection_attempt
+            .map(|t| Instant::now() >= t)
             .unwrap_or(true)
         {
-            self.last_connection_attempt = Some(Instant::now());
             match self.connect() {
                 Ok(stream) => {
                     self.stream = Some(stream);
+                    self.next_connection_attempt = None;
+                    self.backoff.reset();
                     Ok(())
                 }
                 Err(e) => {
                     eprintln!("[Logging] Failed to connect: {}", e);
+                    self.next_connection_attempt = Some(self.backoff.next_attempt());
                     Err(e)
                 }
             }
@@ -151,7 +385,7 @@ impl Write for TcpStreamHandler {
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        if let Some(mut stream) = self.stream.as_ref() {
+        if let Some(stream) = self.stream.as_mut() {
             stream.flush()
         } else {
             Err(io::Error::new(
@@ -0,0 +1,342 @@
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    ffi::OsString,
+    fs,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+const RECORD_HEADER_LEN: u64 = 4;
+
+/// Where a [`TcpWriter`](super::TcpWriter) keeps logs while its endpoint is unreachable.
+///
+/// Logs are queued in memory first. Once `max_memory_bytes` are used they are appended to
+/// `spill_file`, if any, and everything queued is replayed in order after reconnecting.
+/// Logs which fit in neither are dropped and counted by
+/// [`TcpWriter::dropped_events`](super::TcpWriter::dropped_events).
+///
+/// The spill file survives restarts: logs left in it are replayed by the next writer opening
+/// it. A log may then be sent twice if the process stopped in the middle of a replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacklogPolicy {
+    pub max_memory_bytes: usize,
+    pub spill_file: Option<PathBuf>,
+    pub max_spill_bytes: u64,
+}
+
+impl Default for BacklogPolicy {
+    fn default() -> Self {
+        Self {
+            max_memory_bytes: 1024 * 1024,
+            spill_file: None,
+            max_spill_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// Frames waiting for the connection to come back, oldest first.
+pub(crate) struct Backlog {
+    memory: VecDeque<Vec<u8>>,
+    memory_bytes: usize,
+    max_memory_bytes: usize,
+    /// Always holds newer frames than `memory`.
+    spill: Option<SpillFile>,
+    max_spill_bytes: u64,
+}
+
+impl Backlog {
+    pub(crate) fn open(policy: &BacklogPolicy) -> io::Result<Self> {
+        let spill = match policy.spill_file.as_ref() {
+            Some(path) => Some(SpillFile::open(path)?),
+            None => None,
+        };
+        Ok(Self {
+            memory: VecDeque::new(),
+            memory_bytes: 0,
+            max_memory_bytes: policy.max_memory_bytes,
+            spill,
+            max_spill_bytes: policy.max_spill_bytes,
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.memory.is_empty() && self.spill.as_ref().map(|s| s.is_empty()).unwrap_or(true)
+    }
+
+    /// Queue `frame` behind everything queued so far, returns `false` if it had to be dropped.
+    pub(crate) fn push(&mut self, frame: Vec<u8>) -> bool {
+        let spilling = self.spill.as_ref().map(|s| !s.is_empty()).unwrap_or(false);
+        if !spilling && self.memory_bytes + frame.len() <= self.max_memory_bytes {
+            self.memory_bytes += frame.len();
+            self.memory.push_back(frame);
+            return true;
+        }
+        let max_spill_bytes = self.max_spill_bytes;
+        match self.spill.as_mut() {
+            Some(spill)
+                if spill.len() + RECORD_HEADER_LEN + frame.len() as u64 <= max_spill_bytes =>
+            {
+                match spill.push(&frame, max_spill_bytes) {
+                    Ok(()) => true,
+                    Err(err) => {
+                        eprintln!(
+                            "[Logging] Unable to write to spill file({}): {}",
+                            spill.path.display(),
+                            err
+                        );
+                        false
+                    }
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Hand the queued frames to `send` in order until it fails, removing the ones it accepted.
+    pub(crate) fn replay<F>(&mut self, mut send: F) -> io::Result<()>
+    where
+        F: FnMut(&[u8]) -> io::Result<()>,
+    {
+        while let Some(frame) = self.memory.front() {
+            send(frame)?;
+            self.memory_bytes -= frame.len();
+            self.memory.pop_front();
+        }
+        if let Some(spill) = self.spill.as_mut() {
+            while let Some(frame) = spill.front()? {
+                send(&frame)?;
+                spill.pop(&frame)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Append-only file of length-prefixed frames, truncated once everything has been replayed
+/// and compacted when replayed frames at its start take the room of new ones.
+struct SpillFile {
+    path: PathBuf,
+    file: fs::File,
+    read_offset: u64,
+    write_offset: u64,
+}
+
+impl SpillFile {
+    fn open(path: &Path) -> io::Result<Self> {
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let write_offset = complete_records_len(&mut file)?;
+        // drop a record cut short by a crash so new ones are not appended behind it.
+        file.set_len(write_offset)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            read_offset: 0,
+            write_offset,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.read_offset == self.write_offset
+    }
+
+    /// Bytes of the records which have not been replayed yet.
+    fn len(&self) -> u64 {
+        self.write_offset - self.read_offset
+    }
+
+    /// Append `frame`, compacting the file first if it would grow past `max_file_len`.
+    fn push(&mut self, frame: &[u8], max_file_len: u64) -> io::Result<()> {
+        let len = u32::try_from(frame.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame too large"))?;
+        let mut record = Vec::with_capacity(frame.len() + RECORD_HEADER_LEN as usize);
+        record.extend_from_slice(&len.to_be_bytes());
+        record.extend_from_slice(frame);
+        if self.read_offset > 0 && self.write_offset + record.len() as u64 > max_file_len {
+            self.compact()?;
+        }
+        self.file.seek(SeekFrom::Start(self.write_offset))?;
+        self.file.write_all(&record)?;
+        self.write_offset += record.len() as u64;
+        Ok(())
+    }
+
+    fn front(&mut self) -> io::Result<Option<Vec<u8>>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+        let mut header = [0u8; RECORD_HEADER_LEN as usize];
+        self.file.seek(SeekFrom::Start(self.read_offset))?;
+        let len = match self.file.read_exact(&mut header) {
+            Ok(()) => u64::from(u32::from_be_bytes(header)),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => 0,
+            Err(err) => return Err(err),
+        };
+        if len == 0 || self.read_offset + RECORD_HEADER_LEN + len > self.write_offset {
+            // the file has been modified behind our back, nothing in it can be trusted.
+            self.clear()?;
+            return Ok(None);
+        }
+        let mut frame = vec![0u8; len as usize];
+        self.file.read_exact(&mut frame)?;
+        Ok(Some(frame))
+    }
+
+    fn pop(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.read_offset += RECORD_HEADER_LEN + frame.len() as u64;
+        if self.is_empty() {
+            self.clear()?;
+        }
+        Ok(())
+    }
+
+    /// Copy the records not replayed yet into a new file which then replaces this one, so
+    /// that a crash in between leaves either file whole.
+    fn compact(&mut self) -> io::Result<()> {
+        let mut staging_path = OsString::from(&self.path);
+        staging_path.push(".compacting");
+        let mut staging = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&staging_path)?;
+        let len = self.len();
+        self.file.seek(SeekFrom::Start(self.read_offset))?;
+        io::copy(&mut (&mut self.file).take(len), &mut staging)?;
+        fs::rename(&staging_path, &self.path)?;
+        self.file = staging;
+        self.read_offset = 0;
+        self.write_offset = len;
+        Ok(())
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.file.set_len(0)?;
+        self.read_offset = 0;
+        self.write_offset = 0;
+        Ok(())
+    }
+}
+
+/// Length of the leading part of `file` which consists of complete records.
+fn complete_records_len(file: &mut fs::File) -> io::Result<u64> {
+    let len = file.metadata()?.len();
+    let mut offset = 0;
+    let mut header = [0u8; RECORD_HEADER_LEN as usize];
+    while offset + RECORD_HEADER_LEN <= len {
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut header)?;
+        let record_len = RECORD_HEADER_LEN + u64::from(u32::from_be_bytes(header));
+        if offset + record_len > len {
+            break;
+        }
+        offset += record_len;
+    }
+    Ok(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spill_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "logger-backlog-{}-{}.spill",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn backlog(path: &Path, max_memory_bytes: usize, max_spill_bytes: u64) -> Backlog {
+        Backlog::open(&BacklogPolicy {
+            max_memory_bytes,
+            spill_file: Some(path.to_path_buf()),
+            max_spill_bytes,
+        })
+        .unwrap()
+    }
+
+    /// Replay everything, or only `limit` frames before failing.
+    fn replay(backlog: &mut Backlog, limit: Option<usize>) -> Vec<String> {
+        let mut sent = vec![];
+        let _ = backlog.replay(|frame| {
+            if Some(sent.len()) == limit {
+                return Err(io::Error::new(io::ErrorKind::Other, "disconnected"));
+            }
+            sent.push(String::from_utf8(frame.to_vec()).unwrap());
+            Ok(())
+        });
+        sent
+    }
+
+    #[test]
+    fn replays_memory_then_spill_in_order() {
+        let path = spill_path("order");
+        let mut backlog = backlog(&path, 8, 1024);
+        for frame in ["m0", "m1", "m2", "m3", "s4", "s5"] {
+            assert!(backlog.push(frame.as_bytes().to_vec()));
+        }
+        assert_eq!(backlog.memory.len(), 4);
+        // once spilling, newer frames go behind the spilled ones even if memory frees up.
+        assert_eq!(replay(&mut backlog, Some(1)), ["m0"]);
+        assert!(backlog.push(b"s6".to_vec()));
+
+        assert_eq!(
+            replay(&mut backlog, None),
+            ["m1", "m2", "m3", "s4", "s5", "s6"]
+        );
+        assert!(backlog.is_empty());
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn replayed_frames_do_not_count_against_the_spill_bound() {
+        let path = spill_path("bound");
+        // room for three records of 4 + 2 bytes.
+        let mut backlog = backlog(&path, 0, 18);
+        for frame in ["a0", "a1", "a2"] {
+            assert!(backlog.push(frame.as_bytes().to_vec()));
+        }
+        assert!(!backlog.push(b"a3".to_vec()));
+
+        assert_eq!(replay(&mut backlog, Some(2)), ["a0", "a1"]);
+        assert!(backlog.push(b"a4".to_vec()));
+        assert!(backlog.push(b"a5".to_vec()));
+        assert!(!backlog.push(b"a6".to_vec()));
+        assert!(fs::metadata(&path).unwrap().len() <= 18);
+
+        assert_eq!(replay(&mut backlog, None), ["a2", "a4", "a5"]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn spilled_frames_survive_a_restart() {
+        let path = spill_path("restart");
+        {
+            let mut backlog = backlog(&path, 0, 1024);
+            assert!(backlog.push(b"r0".to_vec()));
+            assert!(backlog.push(b"r1".to_vec()));
+        }
+        // a record cut short by a crash is dropped.
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[0, 0, 0, 9, b'x'])
+            .unwrap();
+
+        let mut backlog = backlog(&path, 0, 1024);
+        assert!(backlog.push(b"r2".to_vec()));
+        assert_eq!(replay(&mut backlog, None), ["r0", "r1", "r2"]);
+        fs::remove_file(&path).unwrap();
+    }
+}
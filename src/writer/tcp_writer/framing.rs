@@ -0,0 +1,49 @@
+/// How consecutive log lines are delimited on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcpFraming {
+    /// Terminate every line with `\n`, as expected by most line based inputs.
+    NewlineDelimited,
+    /// Prefix every line with its length in ASCII digits and a space (RFC 6587 octet counting).
+    OctetCounting,
+    /// Prefix every line with its length as a 4-byte big-endian integer.
+    LengthPrefixed,
+    /// Terminate every line with a null byte, as expected by GELF tcp inputs.
+    NullDelimited,
+}
+
+impl Default for TcpFraming {
+    fn default() -> Self {
+        TcpFraming::NewlineDelimited
+    }
+}
+
+impl TcpFraming {
+    pub(crate) fn frame(&self, log: &str) -> Vec<u8> {
+        let payload = log.as_bytes();
+        match self {
+            TcpFraming::NewlineDelimited => {
+                let mut bytes = Vec::with_capacity(payload.len() + 1);
+                bytes.extend_from_slice(payload);
+                bytes.push(b'\n');
+                bytes
+            }
+            TcpFraming::OctetCounting => {
+                let mut bytes = format!("{} ", payload.len()).into_bytes();
+                bytes.extend_from_slice(payload);
+                bytes
+            }
+            TcpFraming::LengthPrefixed => {
+                let mut bytes = Vec::with_capacity(payload.len() + 4);
+                bytes.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+                bytes.extend_from_slice(payload);
+                bytes
+            }
+            TcpFraming::NullDelimited => {
+                let mut bytes = Vec::with_capacity(payload.len() + 1);
+                bytes.extend_from_slice(payload);
+                bytes.push(0);
+                bytes
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, Instant},
+};
+
+/// How long a writer waits before trying to reconnect after a failed attempt.
+///
+/// The delay starts at `initial_delay` and is multiplied by `multiplier` after every
+/// failure up to `max_delay`. `jitter` is the fraction of the delay which is randomized,
+/// e.g. `0.5` waits anywhere between 50% and 100% of it, so that many writers losing the
+/// same collector do not reconnect in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    pub jitter: f64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            multiplier: 2.0,
+            jitter: 0.5,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Retry at a fixed `delay` without jitter.
+    pub fn fixed(delay: Duration) -> Self {
+        Self {
+            initial_delay: delay,
+            max_delay: delay,
+            multiplier: 1.0,
+            jitter: 0.0,
+        }
+    }
+}
+
+/// Delay state of a single connection following a [`ReconnectPolicy`].
+pub(crate) struct Backoff {
+    policy: ReconnectPolicy,
+    delay: Duration,
+}
+
+impl Backoff {
+    pub(crate) fn new(policy: ReconnectPolicy) -> Self {
+        Self {
+            policy,
+            delay: policy.initial_delay.min(policy.max_delay),
+        }
+    }
+
+    /// When to try again after a failed attempt, grows the delay of the next one.
+    pub(crate) fn next_attempt(&mut self) -> Instant {
+        let now = Instant::now();
+        let mut delay = self.next_delay();
+        // a delay near `Duration::MAX` is further out than any instant.
+        loop {
+            match now.checked_add(delay) {
+                Some(attempt) => return attempt,
+                None => delay /= 2,
+            }
+        }
+    }
+
+    /// The delay to wait after a failed attempt, grows the next one.
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.delay;
+        let next = delay.as_secs_f64() * self.policy.multiplier.max(1.0);
+        self.delay = secs_up_to(next, self.policy.max_delay);
+
+        let jitter = if self.policy.jitter > 0.0 {
+            self.policy.jitter.min(1.0)
+        } else {
+            0.0
+        };
+        secs_up_to(
+            delay.as_secs_f64() * (1.0 - jitter * random_fraction()),
+            delay,
+        )
+    }
+
+    /// Start over from the initial delay once connected.
+    pub(crate) fn reset(&mut self) {
+        self.delay = self.policy.initial_delay.min(self.policy.max_delay);
+    }
+}
+
+/// `secs` as a duration, or `max` when it is longer, infinite or NaN.
+fn secs_up_to(secs: f64, max: Duration) -> Duration {
+    // below `max` as f64 also keeps it below `u64::MAX` seconds, where the conversion panics.
+    if secs < max.as_secs_f64() {
+        Duration::from_secs_f64(secs)
+    } else {
+        max
+    }
+}
+
+/// A number in `[0, 1)`, good enough to spread reconnects but not for anything else.
+fn random_fraction() -> f64 {
+    // every RandomState is seeded differently, per process and per call.
+    let hasher = RandomState::new().build_hasher();
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,127 @@
+use rustls::{
+    Certificate, ClientConfig, ClientConnection, PrivateKey, RootCertStore, ServerName, StreamOwned,
+};
+use std::{
+    convert::TryFrom,
+    fs, io,
+    net::TcpStream,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+pub(crate) type TlsStream = StreamOwned<ClientConnection, TcpStream>;
+
+/// TLS settings of a [`TcpWriter`](super::TcpWriter).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TcpTlsConfig {
+    /// Name the server certificate is verified against, the host part of the endpoint by default.
+    pub server_name: Option<String>,
+    /// PEM bundle of the trusted CAs, the platform's native roots are used if not set.
+    pub ca_file: Option<PathBuf>,
+    /// PEM certificate chain presented to the server for mutual TLS, requires `client_key`.
+    pub client_cert: Option<PathBuf>,
+    /// PEM private key (PKCS#8, PKCS#1 or SEC1) matching `client_cert`.
+    pub client_key: Option<PathBuf>,
+}
+
+/// Wraps freshly connected sockets into TLS sessions.
+pub(crate) struct TlsConnector {
+    config: Arc<ClientConfig>,
+    server_name: ServerName,
+}
+
+impl TlsConnector {
+    pub(crate) fn new(tls: &TcpTlsConfig, endpoint: &str) -> io::Result<Self> {
+        let server_name = tls.server_name.as_deref().unwrap_or_else(|| host(endpoint));
+        let server_name = ServerName::try_from(server_name).map_err(|_| {
+            invalid_input(format!("{} is not a valid tls server name", server_name))
+        })?;
+
+        let mut roots = RootCertStore::empty();
+        let certs = match tls.ca_file.as_deref() {
+            Some(ca_file) => load_certs(ca_file)?.into_iter().map(|c| c.0).collect(),
+            None => rustls_native_certs::load_native_certs()?
+                .into_iter()
+                .map(|c| c.0)
+                .collect::<Vec<_>>(),
+        };
+        roots.add_parsable_certificates(&certs);
+        if roots.is_empty() {
+            return Err(invalid_input(
+                "no trusted tls root certificate found".to_owned(),
+            ));
+        }
+
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots);
+        let config = match (tls.client_cert.as_deref(), tls.client_key.as_deref()) {
+            (Some(cert), Some(key)) => builder
+                .with_single_cert(load_certs(cert)?, load_private_key(key)?)
+                .map_err(|e| invalid_input(e.to_string()))?,
+            (None, None) => builder.with_no_client_auth(),
+            _ => {
+                return Err(invalid_input(
+                    "client_cert and client_key must be set together".to_owned(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            config: Arc::new(config),
+            server_name,
+        })
+    }
+
+    /// Run the handshake on `stream` so a rejected certificate surfaces as a connection error.
+    pub(crate) fn connect(&self, mut stream: TcpStream) -> io::Result<TlsStream> {
+        stream.set_read_timeout(stream.write_timeout()?)?;
+        let mut connection = ClientConnection::new(self.config.clone(), self.server_name.clone())
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        while connection.is_handshaking() {
+            connection.complete_io(&mut stream)?;
+        }
+        Ok(StreamOwned::new(connection, stream))
+    }
+}
+
+/// `host` of `host:port` or `[host]:port`.
+fn host(endpoint: &str) -> &str {
+    let host = endpoint
+        .rsplit_once(':')
+        .map(|(host, _)| host)
+        .unwrap_or(endpoint);
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+fn load_certs(path: &Path) -> io::Result<Vec<Certificate>> {
+    let mut reader = io::BufReader::new(fs::File::open(path)?);
+    let certs = rustls_pemfile::certs(&mut reader)?;
+    if certs.is_empty() {
+        return Err(invalid_input(format!(
+            "no certificate found in {}",
+            path.display()
+        )));
+    }
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+fn load_private_key(path: &Path) -> io::Result<PrivateKey> {
+    let mut reader = io::BufReader::new(fs::File::open(path)?);
+    while let Some(item) = rustls_pemfile::read_one(&mut reader)? {
+        match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => return Ok(PrivateKey(key)),
+            _ => continue,
+        }
+    }
+    Err(invalid_input(format!(
+        "no private key found in {}",
+        path.display()
+    )))
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
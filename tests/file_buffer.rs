@@ -0,0 +1,76 @@
+use logger::{default::DefaultLoggerBuilder, info, prelude::FileWriter, Level, Writer};
+use std::{
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+fn log_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("logger-buffer-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("app.log")
+}
+
+fn read(path: &PathBuf) -> String {
+    fs::read_to_string(path).unwrap()
+}
+
+/// Wait up to `timeout` for the file at `path` to read `expected`.
+fn wait_for(path: &PathBuf, expected: &str, timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        if read(path) == expected {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    false
+}
+
+#[test]
+fn global_flush_writes_buffered_lines() {
+    let path = log_path("global");
+    DefaultLoggerBuilder::new()
+        .is_async(false)
+        .level(Level::Info)
+        .file(&path)
+        .file_buffer(64 * 1024, Duration::from_secs(3600))
+        .try_build()
+        .unwrap();
+
+    info!("buffered");
+    assert_eq!(read(&path), "");
+    logger::flush();
+    let contents = read(&path);
+    assert!(contents.ends_with("buffered\n"), "{:?}", contents);
+    assert_eq!(contents.lines().count(), 1);
+}
+
+#[test]
+fn buffered_lines_are_flushed_after_the_interval() {
+    let path = log_path("interval");
+    let writer = FileWriter::builder(&path)
+        .buffer(64 * 1024, Duration::from_millis(50))
+        .build();
+
+    writer.write("first".to_owned());
+    writer.write("second".to_owned());
+    assert_eq!(read(&path), "");
+    // nothing else is logged, the flushing thread has to pick the lines up.
+    assert!(wait_for(&path, "first\nsecond\n", Duration::from_secs(2)));
+}
+
+#[test]
+fn zero_interval_still_flushes() {
+    let path = log_path("zero");
+    let writer = FileWriter::builder(&path)
+        .buffer(64 * 1024, Duration::ZERO)
+        .build();
+
+    writer.write("first".to_owned());
+    assert!(wait_for(&path, "first\n", Duration::from_secs(2)));
+    drop(writer);
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "loki")]
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use logger::{prelude::HttpWriter, BatchFormat, BatchPolicy, RetryPolicy, Writer};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Start an endpoint answering the first request with `503 Service Unavailable` and
+/// every other one with `204 No Content`, the logs of every request are reported.
+fn unavailable_once() -> (SocketAddr, mpsc::Receiver<usize>) {
+    let (addr_tx, addr_rx) = mpsc::channel();
+    let (tx, rx) = mpsc::channel();
+    let is_unavailable = Arc::new(AtomicBool::new(true));
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async move {
+            let make_service = make_service_fn(move |_| {
+                let tx = tx.clone();
+                let is_unavailable = Arc::clone(&is_unavailable);
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                        let tx = tx.clone();
+                        let is_unavailable = Arc::clone(&is_unavailable);
+                        async move {
+                            let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                            let logs = body.split(|byte| *byte == b'\n').filter(|line| !line.is_empty());
+                            tx.send(logs.count()).unwrap();
+                            let mut response = Response::new(Body::empty());
+                            *response.status_mut() = if is_unavailable.swap(false, Ordering::SeqCst)
+                            {
+                                StatusCode::SERVICE_UNAVAILABLE
+                            } else {
+                                StatusCode::NO_CONTENT
+                            };
+                            Ok::<_, Infallible>(response)
+                        }
+                    }))
+                }
+            });
+            let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+            addr_tx.send(server.local_addr()).unwrap();
+            server.await.unwrap();
+        });
+    });
+    (addr_rx.recv().unwrap(), rx)
+}
+
+#[test]
+fn logs_are_queued_while_a_request_backs_off() {
+    let (addr, requests) = unavailable_once();
+    let writer = HttpWriter::builder(format!("http://{}/", addr))
+        .unwrap()
+        .batch(BatchPolicy {
+            max_items: 100,
+            max_bytes: 1 << 20,
+            max_interval: Duration::from_millis(10),
+            format: BatchFormat::NdJson,
+        })
+        .retry(RetryPolicy {
+            max_retries: 1,
+            initial_delay: Duration::from_secs(1),
+            ..RetryPolicy::none()
+        })
+        .build();
+
+    writer.write("{\"n\":0}".to_owned());
+    assert_eq!(
+        requests.recv_timeout(Duration::from_secs(5)).unwrap(),
+        1,
+        "the first request is refused and retried a second later"
+    );
+    // more than the queue holds on its own, well within the backoff. The worker keeps
+    // taking them off the queue meanwhile.
+    for n in 1..=15_000 {
+        writer.write(format!("{{\"n\":{}}}", n));
+        if n % 1_000 == 0 {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+    writer.flush();
+
+    assert_eq!(writer.dropped_events(), 0);
+    drop(writer);
+    // the retried first log and every other one.
+    assert_eq!(requests.try_iter().sum::<usize>(), 1 + 15_000);
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "loki")]
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server,
+};
+use logger::{default::DefaultLoggerBuilder, BatchEncoder, BatchFormat, BatchPolicy, Level};
+use logger::{info, warn, LokiEncoder};
+use serde_json::{json, Value};
+use std::{convert::Infallible, net::SocketAddr, sync::mpsc, thread, time::Duration};
+
+/// Start a Loki stand-in answering `204 No Content`, every request is reported as its
+/// path and body.
+fn push_endpoint() -> (SocketAddr, mpsc::Receiver<(String, Value)>) {
+    let (addr_tx, addr_rx) = mpsc::channel();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async move {
+            let make_service = make_service_fn(move |_| {
+                let tx = tx.clone();
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                        let tx = tx.clone();
+                        async move {
+                            let path = request.uri().path().to_owned();
+                            let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                            tx.send((path, serde_json::from_slice(&body).unwrap()))
+                                .unwrap();
+                            let mut response = Response::new(Body::empty());
+                            *response.status_mut() = hyper::StatusCode::NO_CONTENT;
+                            Ok::<_, Infallible>(response)
+                        }
+                    }))
+                }
+            });
+            let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+            addr_tx.send(server.local_addr()).unwrap();
+            server.await.unwrap();
+        });
+    });
+    (addr_rx.recv().unwrap(), rx)
+}
+
+#[test]
+fn pushes_batches_grouped_by_labels() {
+    let (addr, requests) = push_endpoint();
+    DefaultLoggerBuilder::new()
+        .is_async(false)
+        .level(Level::Info)
+        .loki_url(format!("http://{}/", addr))
+        .loki_label("app".to_owned(), "demo".to_owned())
+        .loki_batch(BatchPolicy {
+            max_items: 100,
+            max_bytes: 1 << 20,
+            max_interval: Duration::from_secs(60),
+            format: BatchFormat::NdJson,
+        })
+        .try_build()
+        .unwrap();
+
+    let before = chrono::Utc::now().timestamp_nanos();
+    info!("one");
+    warn!("two");
+    info!("three");
+    logger::flush();
+    let after = chrono::Utc::now().timestamp_nanos();
+
+    let (path, body) = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(path, "/loki/api/v1/push");
+    let streams = body["streams"].as_array().unwrap();
+    assert_eq!(streams.len(), 2, "{}", body);
+
+    let info = &streams[0];
+    assert_eq!(info["stream"]["app"], "demo");
+    assert_eq!(info["stream"]["level"], "info");
+    assert_eq!(info["stream"]["target"], "loki");
+    let values = info["values"].as_array().unwrap();
+    assert_eq!(values.len(), 2);
+    let messages: Vec<Value> = values
+        .iter()
+        .map(|value| serde_json::from_str::<Value>(value[1].as_str().unwrap()).unwrap())
+        .map(|line| line["message"].clone())
+        .collect();
+    assert_eq!(messages, [json!("one"), json!("three")]);
+    for value in values {
+        let nanos: i64 = value[0].as_str().unwrap().parse().unwrap();
+        assert!(before <= nanos && nanos <= after, "{} not in {}..{}", nanos, before, after);
+    }
+
+    assert_eq!(streams[1]["stream"]["level"], "warn");
+    assert_eq!(streams[1]["values"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn encoder_merges_streams_with_the_same_labels() {
+    let stream = |level: &str, nanos: &str, line: &str| {
+        json!({ "stream": { "level": level }, "values": [[nanos, line]] }).to_string()
+    };
+    let body = LokiEncoder.encode(&[
+        stream("info", "1", "a"),
+        stream("error", "2", "b"),
+        stream("info", "3", "c"),
+        "not a stream".to_owned(),
+    ]);
+
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body,
+        json!({ "streams": [
+            { "stream": { "level": "info" }, "values": [["1", "a"], ["3", "c"]] },
+            { "stream": { "level": "error" }, "values": [["2", "b"]] },
+        ] })
+    );
+}
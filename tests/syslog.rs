@@ -0,0 +1,120 @@
+#![cfg(all(unix, feature = "syslog"))]
+
+use logger::{prelude::SyslogWriter, Event, Formatter, KeyValue, Level, Metadata, Schema, Value};
+use logger::{SyslogFacility, SyslogFormat, SyslogFormatter, SyslogTransport, Writer};
+use std::{fs, os::unix::net::UnixDatagram, path::PathBuf, process};
+
+/// A datagram socket bound in a directory of its own, standing in for `/dev/log`.
+struct LogSocket {
+    dir: PathBuf,
+    socket: UnixDatagram,
+}
+
+impl LogSocket {
+    fn bind(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("logger-syslog-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let socket = UnixDatagram::bind(dir.join("log")).unwrap();
+        Self { dir, socket }
+    }
+
+    fn writer(&self) -> SyslogWriter {
+        SyslogWriter::new(&SyslogTransport::Unix(self.dir.join("log"))).unwrap()
+    }
+
+    fn recv(&self) -> String {
+        let mut buf = [0; 8192];
+        let len = self.socket.recv(&mut buf).unwrap();
+        String::from_utf8(buf[..len].to_vec()).unwrap()
+    }
+}
+
+impl Drop for LogSocket {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn metadata(level: Level) -> Metadata {
+    Metadata::new(level, "app::db", "app::db", "src/db.rs", 7, "src/db.rs:7")
+}
+
+fn format(formatter: &SyslogFormatter, level: Level, message: &str, kvs: &[&dyn Schema]) -> String {
+    let metadata = metadata(level);
+    Formatter::format(formatter, &Event::new(&metadata, Some(format_args!("{}", message)), kvs))
+        .unwrap()
+}
+
+#[test]
+fn rfc5424_priority_follows_facility_and_level() {
+    let socket = LogSocket::bind("pri");
+    let writer = socket.writer();
+    let formatter = SyslogFormatter::new(SyslogFormat::Rfc5424, SyslogFacility::Local3, "app".into());
+    // local3 is facility 19, so the priority is 152 plus the severity.
+    let expected = [
+        (Level::Crash, "<154>1 "),
+        (Level::Error, "<155>1 "),
+        (Level::Warn, "<156>1 "),
+        (Level::Info, "<158>1 "),
+        (Level::Debug, "<159>1 "),
+        (Level::Trace, "<159>1 "),
+    ];
+    for (level, prefix) in expected.iter() {
+        writer.write(format(&formatter, *level, "hello", &[]));
+        let message = socket.recv();
+        assert!(message.starts_with(prefix), "{:?}: {}", level, message);
+        let hostname = metadata(*level).hostname().unwrap_or("-").to_owned();
+        assert!(
+            message.ends_with(&format!(" {} app {} - - hello", hostname, process::id())),
+            "{}",
+            message
+        );
+    }
+}
+
+#[test]
+fn rfc5424_structured_data_is_escaped() {
+    let socket = LogSocket::bind("sd");
+    let formatter = SyslogFormatter::new(SyslogFormat::Rfc5424, SyslogFacility::Daemon, "app".into());
+    let quoted = "say \"hi\" [now] c:\\tmp";
+    let user = KeyValue::new("user", Value::from_serde(&"bob"));
+    let note = KeyValue::new("note", Value::from_serde(&quoted));
+    let count = KeyValue::new("a=b c", Value::from_serde(&3));
+    let kvs: [&dyn Schema; 3] = [&user, &note, &count];
+    socket.writer().write(format(&formatter, Level::Warn, "saved", &kvs));
+
+    let message = socket.recv();
+    assert!(message.starts_with("<28>1 "), "{}", message);
+    assert!(
+        message.ends_with(concat!(
+            r#" - [data@32473 abc="3" note="say \"hi\" [now\] c:\\tmp" user="bob"]"#,
+            " saved"
+        )),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn rfc3164_appends_key_values_to_the_message() {
+    let socket = LogSocket::bind("bsd");
+    let formatter = SyslogFormatter::new(SyslogFormat::Rfc3164, SyslogFacility::Daemon, "my app".into())
+        .include_hostname(false);
+    let user = KeyValue::new("user", Value::from_serde(&"bob"));
+    let kvs: [&dyn Schema; 1] = [&user];
+    socket.writer().write(format(&formatter, Level::Error, "boom", &kvs));
+
+    // daemon is facility 3, an error severity 3.
+    let message = socket.recv();
+    assert!(message.starts_with("<27>"), "{}", message);
+    // `Mmm dd hh:mm:ss`, the day padded with a space.
+    let timestamp = &message[4..19];
+    assert_eq!(timestamp.len(), 15);
+    assert_eq!(&timestamp[3..4], " ");
+    assert_eq!(&timestamp[9..10], ":");
+    assert_eq!(
+        &message[19..],
+        format!(" myapp[{}]: boom user=bob", process::id())
+    );
+}
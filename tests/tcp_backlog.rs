@@ -0,0 +1,57 @@
+#![cfg(feature = "tcp")]
+
+use logger::{default::DefaultLoggerBuilder, info, BacklogPolicy, Level, ReconnectPolicy};
+use std::{
+    io::{BufRead, BufReader},
+    net::TcpListener,
+    thread,
+    time::Duration,
+};
+
+#[test]
+fn backlog_is_replayed_in_order_and_drops_are_counted() {
+    // a port nothing listens on until the collector comes back below.
+    let addr = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let spill_file =
+        std::env::temp_dir().join(format!("logger-tcp-backlog-{}.spill", std::process::id()));
+    let _ = std::fs::remove_file(&spill_file);
+    let logger = DefaultLoggerBuilder::new()
+        .is_async(true)
+        .level(Level::Info)
+        .tcp_address(addr.to_string())
+        .tcp_level(Level::Info)
+        .tcp_reconnect(ReconnectPolicy::fixed(Duration::from_millis(50)))
+        .tcp_backlog(BacklogPolicy {
+            // logs are a bit over 1000 bytes: one fits in memory, three are spilled.
+            max_memory_bytes: 1500,
+            spill_file: Some(spill_file.clone()),
+            max_spill_bytes: 4500,
+        })
+        .try_build()
+        .unwrap();
+
+    let padding = "x".repeat(1000);
+    for n in 0..6 {
+        info!(n = n, padding = padding.as_str(), "unreachable");
+    }
+    logger::flush();
+    assert_eq!(logger.tcp_dropped_events(), 2);
+
+    let listener = TcpListener::bind(addr).unwrap();
+    thread::sleep(Duration::from_millis(100));
+    info!(n = 6, "reconnected");
+    logger::flush();
+
+    let (stream, _) = listener.accept().unwrap();
+    let received: Vec<i64> = BufReader::new(stream)
+        .lines()
+        .take(5)
+        .map(|line| {
+            let log: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+            log["data"]["n"].as_i64().unwrap()
+        })
+        .collect();
+    assert_eq!(received, [0, 1, 2, 3, 6]);
+    assert_eq!(logger.tcp_dropped_events(), 2);
+    let _ = std::fs::remove_file(&spill_file);
+}
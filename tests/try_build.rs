@@ -0,0 +1,45 @@
+#![cfg(target_os = "linux")]
+
+use logger::default::{DefaultLoggerBuilder, LoggerBuildError};
+use std::{
+    fs,
+    sync::{Arc, Barrier},
+    thread,
+};
+
+fn thread_count() -> usize {
+    fs::read_dir("/proc/self/task").unwrap().count()
+}
+
+#[test]
+fn only_the_installed_logger_starts_a_service_thread() {
+    let before = thread_count();
+    let barrier = Arc::new(Barrier::new(8));
+    let builders: Vec<_> = (0..8)
+        .map(|_| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                let mut builder = DefaultLoggerBuilder::new();
+                builder.is_async(true);
+                barrier.wait();
+                builder.try_build().err()
+            })
+        })
+        .collect();
+    let errors: Vec<_> = builders
+        .into_iter()
+        .map(|builder| builder.join().unwrap())
+        .collect();
+
+    assert_eq!(errors.iter().filter(|err| err.is_none()).count(), 1);
+    assert!(errors
+        .iter()
+        .flatten()
+        .all(|err| matches!(err, LoggerBuildError::GlobalLoggerAlreadySet)));
+    assert!(matches!(
+        DefaultLoggerBuilder::new().is_async(true).try_build(),
+        Err(LoggerBuildError::GlobalLoggerAlreadySet)
+    ));
+    // the builder threads are gone, only the service thread of the installed logger is left.
+    assert_eq!(thread_count(), before + 1);
+}